@@ -0,0 +1,59 @@
+/// Converts a decimal amount of whole tokens like `2.5` into base units of a
+/// token with `decimals` decimal places.
+///
+/// Parsed by hand rather than as a float so amounts like `0.1` stay exact.
+pub fn parse_ui_amount(amount: &str, decimals: u8) -> Result<u64, String> {
+    let amount = amount.trim();
+    let (whole, fraction) = amount.split_once('.').unwrap_or((amount, ""));
+
+    if (whole.is_empty() && fraction.is_empty())
+        || !whole.chars().all(|c| c.is_ascii_digit())
+        || !fraction.chars().all(|c| c.is_ascii_digit())
+    {
+        return Err(format!("invalid amount `{amount}`"));
+    }
+    if fraction.len() > usize::from(decimals) {
+        return Err(format!("amount `{amount}` has more than {decimals} decimal places"));
+    }
+
+    let too_large = || format!("amount `{amount}` is too large");
+
+    let whole: u64 = if whole.is_empty() { 0 } else { whole.parse().map_err(|_| too_large())? };
+    let unit = 10u64.checked_pow(decimals.into()).ok_or_else(too_large)?;
+    let fraction: u64 = if decimals == 0 {
+        0
+    } else {
+        format!("{fraction:0<width$}", width = usize::from(decimals)).parse().map_err(|_| too_large())?
+    };
+
+    whole
+        .checked_mul(unit)
+        .and_then(|amount| amount.checked_add(fraction))
+        .ok_or_else(too_large)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_whole_and_fractional_amounts() {
+        assert_eq!(parse_ui_amount("100", 6), Ok(100_000_000));
+        assert_eq!(parse_ui_amount("2.5", 6), Ok(2_500_000));
+        assert_eq!(parse_ui_amount(".000001", 6), Ok(1));
+        assert_eq!(parse_ui_amount("0.1", 9), Ok(100_000_000));
+        assert_eq!(parse_ui_amount("7", 0), Ok(7));
+    }
+
+    #[test]
+    fn rejects_invalid_amounts() {
+        assert!(parse_ui_amount("", 6).is_err());
+        assert!(parse_ui_amount(".", 6).is_err());
+        assert!(parse_ui_amount("-1", 6).is_err());
+        assert!(parse_ui_amount("1e3", 6).is_err());
+        assert!(parse_ui_amount("0.0000001", 6).is_err());
+        assert!(parse_ui_amount("1.5", 0).is_err());
+        assert!(parse_ui_amount("18446744073709551616", 0).is_err());
+        assert!(parse_ui_amount("18446744073709.551616", 6).is_err());
+    }
+}
@@ -0,0 +1,179 @@
+use anyhow::{Context, Result};
+use clap::Args;
+use solana_client::rpc_client::RpcClient;
+use std::{fmt, str::FromStr};
+
+const EXPLORER_URL: &str = "https://explorer.solana.com";
+
+const LOCALNET_URL: &str = "http://localhost:8899";
+const DEVNET_URL: &str = "https://api.devnet.solana.com";
+const TESTNET_URL: &str = "https://api.testnet.solana.com";
+const MAINNET_BETA_URL: &str = "https://api.mainnet-beta.solana.com";
+
+/// Cluster the clients talk to, either one of the public clusters, a local
+/// validator or any other JSON RPC URL.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Cluster {
+    Localnet,
+    Devnet,
+    Testnet,
+    MainnetBeta,
+    Custom(String),
+}
+
+impl Cluster {
+    pub fn url(&self) -> &str {
+        match self {
+            Cluster::Localnet => LOCALNET_URL,
+            Cluster::Devnet => DEVNET_URL,
+            Cluster::Testnet => TESTNET_URL,
+            Cluster::MainnetBeta => MAINNET_BETA_URL,
+            Cluster::Custom(url) => url,
+        }
+    }
+
+    pub fn rpc_client(&self) -> RpcClient {
+        RpcClient::new(self.url().to_string())
+    }
+
+    /// Explorer link of a transaction on this cluster.
+    pub fn tx_explorer_url(&self, signature: &str) -> String {
+        self.explorer_url(&format!("tx/{signature}"))
+    }
+
+    /// Explorer link of an account on this cluster.
+    pub fn address_explorer_url(&self, address: &str) -> String {
+        self.explorer_url(&format!("address/{address}"))
+    }
+
+    fn explorer_url(&self, path: &str) -> String {
+        match self {
+            Cluster::MainnetBeta => format!("{EXPLORER_URL}/{path}"),
+            Cluster::Devnet => format!("{EXPLORER_URL}/{path}?cluster=devnet"),
+            Cluster::Testnet => format!("{EXPLORER_URL}/{path}?cluster=testnet"),
+            Cluster::Localnet | Cluster::Custom(_) => format!(
+                "{EXPLORER_URL}/{path}?cluster=custom&customUrl={}",
+                encode_query_value(self.url())
+            ),
+        }
+    }
+
+    fn from_url(url: &str) -> Cluster {
+        match url.trim_end_matches('/') {
+            LOCALNET_URL | "http://127.0.0.1:8899" => Cluster::Localnet,
+            DEVNET_URL => Cluster::Devnet,
+            TESTNET_URL => Cluster::Testnet,
+            MAINNET_BETA_URL => Cluster::MainnetBeta,
+            _ => Cluster::Custom(url.to_string()),
+        }
+    }
+}
+
+impl FromStr for Cluster {
+    type Err = String;
+
+    /// Accepts the same monikers as the Solana CLI `--url` flag, or a URL.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "l" | "localhost" | "localnet" => Ok(Cluster::Localnet),
+            "d" | "devnet" => Ok(Cluster::Devnet),
+            "t" | "testnet" => Ok(Cluster::Testnet),
+            "m" | "mainnet-beta" | "mainnet" => Ok(Cluster::MainnetBeta),
+            url if url.starts_with("http://") || url.starts_with("https://") => Ok(Cluster::from_url(url)),
+            _ => Err(format!(
+                "invalid cluster `{s}`, expected localnet, devnet, testnet, mainnet-beta or a URL"
+            )),
+        }
+    }
+}
+
+impl fmt::Display for Cluster {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Cluster::Localnet => f.write_str("localnet"),
+            Cluster::Devnet => f.write_str("devnet"),
+            Cluster::Testnet => f.write_str("testnet"),
+            Cluster::MainnetBeta => f.write_str("mainnet-beta"),
+            Cluster::Custom(url) => f.write_str(url),
+        }
+    }
+}
+
+/// `--url` flag shared by the clients. Without it the cluster comes from the
+/// `SOLANA_CLUSTER` environment variable, then from the `json_rpc_url` of the
+/// Solana CLI config file, and finally defaults to devnet.
+#[derive(Args, Clone, Debug, Default)]
+pub struct ClusterArgs {
+    /// Cluster to use: localnet, devnet, testnet, mainnet-beta or a JSON RPC URL
+    #[arg(short = 'u', long = "url", env = "SOLANA_CLUSTER", global = true)]
+    cluster: Option<Cluster>,
+}
+
+impl ClusterArgs {
+    pub fn resolve(&self) -> Result<Cluster> {
+        if let Some(cluster) = &self.cluster {
+            return Ok(cluster.clone());
+        }
+
+        let Some(config_file) = solana_cli_config::CONFIG_FILE.as_ref() else {
+            return Ok(Cluster::Devnet);
+        };
+
+        if !std::path::Path::new(config_file).exists() {
+            return Ok(Cluster::Devnet);
+        }
+
+        let config = solana_cli_config::Config::load(config_file)
+            .with_context(|| format!("Unable to load Solana CLI config {config_file}"))?;
+
+        Ok(Cluster::from_url(&config.json_rpc_url))
+    }
+}
+
+/// Percent-encodes everything but the unreserved characters of RFC 3986.
+fn encode_query_value(value: &str) -> String {
+    value
+        .bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => (byte as char).to_string(),
+            _ => format!("%{byte:02X}"),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SIGNATURE: &str = "5iiuLMQ1DNq9menSQKtFRSrGpRU5GJYLrd4QpnsgASCDb1xyyMsTF3YdRxyqWh4f51x1JiCJyw7WjFAPpbd3dnz";
+
+    #[test]
+    fn parses_monikers_and_urls() {
+        assert_eq!("d".parse(), Ok(Cluster::Devnet));
+        assert_eq!("mainnet-beta".parse(), Ok(Cluster::MainnetBeta));
+        assert_eq!("localhost".parse(), Ok(Cluster::Localnet));
+        assert_eq!("https://api.testnet.solana.com/".parse(), Ok(Cluster::Testnet));
+        assert_eq!("http://127.0.0.1:8899".parse(), Ok(Cluster::Localnet));
+        assert_eq!(
+            "https://rpc.example.com".parse(),
+            Ok(Cluster::Custom("https://rpc.example.com".to_string()))
+        );
+        assert!("devnt".parse::<Cluster>().is_err());
+    }
+
+    #[test]
+    fn explorer_urls() {
+        assert_eq!(
+            Cluster::Devnet.tx_explorer_url(SIGNATURE),
+            format!("https://explorer.solana.com/tx/{SIGNATURE}?cluster=devnet")
+        );
+        assert_eq!(
+            Cluster::MainnetBeta.address_explorer_url("63EEC9FfGyksm7PkVC6z8uAmqozbQcTzbkWJNsgqjkFs"),
+            "https://explorer.solana.com/address/63EEC9FfGyksm7PkVC6z8uAmqozbQcTzbkWJNsgqjkFs"
+        );
+        assert_eq!(
+            Cluster::Localnet.tx_explorer_url(SIGNATURE),
+            format!("https://explorer.solana.com/tx/{SIGNATURE}?cluster=custom&customUrl=http%3A%2F%2Flocalhost%3A8899")
+        );
+    }
+}
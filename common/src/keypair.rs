@@ -0,0 +1,360 @@
+use bip39::{Language, Mnemonic};
+use clap::Args;
+use solana_sdk::{
+    derivation_path::DerivationPath,
+    signature::{
+        generate_seed_from_seed_phrase_and_passphrase, keypair_from_seed, keypair_from_seed_and_derivation_path,
+        Keypair,
+    },
+};
+use std::{
+    fs,
+    io::Write,
+    path::{Path, PathBuf},
+};
+use thiserror::Error;
+
+/// Word counts allowed for a BIP39 seed phrase.
+const SEED_PHRASE_WORD_COUNTS: [usize; 5] = [12, 15, 18, 21, 24];
+
+#[derive(Debug, Error)]
+pub enum KeypairError {
+    #[error("Unable to read keypair file {}", path.display())]
+    Read {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+
+    #[error(
+        "Keypair file {} is readable by every user (mode {mode:o}), restrict it with `chmod 600 {}`",
+        path.display(),
+        path.display()
+    )]
+    WorldReadable { path: PathBuf, mode: u32 },
+
+    #[error("Unable to parse keypair file {}: {reason}", path.display())]
+    Parse { path: PathBuf, reason: String },
+
+    #[error("Unable to write keypair file {}", path.display())]
+    Write {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+}
+
+/// `--keypair` and `--derivation-path` flags shared by the clients.
+#[derive(Args, Clone, Debug)]
+pub struct KeypairArgs {
+    /// Keypair file of the fee payer: a JSON byte array, a base58 secret key or a seed phrase
+    #[arg(short = 'k', long = "keypair", default_value = "../payer-keypair.json", global = true)]
+    pub keypair: String,
+
+    /// Derivation path used when the keypair file holds a seed phrase, e.g. m/44'/501'/0'/0'
+    #[arg(long, global = true)]
+    pub derivation_path: Option<String>,
+
+    /// BIP39 passphrase of the seed phrase, if it was created with one
+    #[arg(long, env = "KEYPAIR_PASSPHRASE", hide_env_values = true, global = true)]
+    pub passphrase: Option<String>,
+}
+
+impl KeypairArgs {
+    pub fn read_keypair(&self) -> Result<Keypair, KeypairError> {
+        read_keypair_file_with_passphrase(
+            &self.keypair,
+            self.derivation_path.as_deref(),
+            self.passphrase.as_deref().unwrap_or_default(),
+        )
+    }
+}
+
+/// Reads a keypair from `path`, expanding a leading `~` to the home directory.
+///
+/// The file may hold the JSON byte array written by `solana-keygen`, a base58
+/// encoded secret key as exported by wallets, or a BIP39 seed phrase. Seed
+/// phrases are derived with `derivation_path` if given, and otherwise use the
+/// seed directly like `solana-keygen recover` does. Files readable by every
+/// user are refused.
+pub fn read_keypair_file(path: &str, derivation_path: Option<&str>) -> Result<Keypair, KeypairError> {
+    read_keypair_file_with_passphrase(path, derivation_path, "")
+}
+
+/// [`read_keypair_file`] for a seed phrase protected by a BIP39 `passphrase`,
+/// which is ignored by the other formats.
+pub fn read_keypair_file_with_passphrase(
+    path: &str,
+    derivation_path: Option<&str>,
+    passphrase: &str,
+) -> Result<Keypair, KeypairError> {
+    let path = expand_tilde(path, std::env::var_os("HOME").map(PathBuf::from));
+
+    check_permissions(&path)?;
+
+    let contents = fs::read_to_string(&path).map_err(|source| KeypairError::Read {
+        path: path.clone(),
+        source,
+    })?;
+
+    parse_keypair(&contents, derivation_path, passphrase).map_err(|reason| KeypairError::Parse { path, reason })
+}
+
+/// Saves `keypair` to a new file at `path` as the JSON byte array written by
+/// `solana-keygen`, readable by its owner only. Existing files are never
+/// overwritten, so a keypair holding an authority can't be lost by mistake.
+pub fn write_keypair_file(path: &str, keypair: &Keypair) -> Result<PathBuf, KeypairError> {
+    let path = expand_tilde(path, std::env::var_os("HOME").map(PathBuf::from));
+
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create_new(true);
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+
+    let contents = format!("{:?}", keypair.to_bytes().to_vec());
+
+    options
+        .open(&path)
+        .and_then(|mut file| file.write_all(contents.as_bytes()))
+        .map_err(|source| KeypairError::Write {
+            path: path.clone(),
+            source,
+        })?;
+
+    Ok(path)
+}
+
+fn parse_keypair(contents: &str, derivation_path: Option<&str>, passphrase: &str) -> Result<Keypair, String> {
+    let contents = contents.trim();
+
+    if contents.starts_with('[') {
+        let bytes = serde_json::from_str::<Vec<u8>>(contents)
+            .map_err(|err| format!("expected a JSON array of bytes: {err}"))?;
+
+        return Keypair::from_bytes(&bytes).map_err(|err| err.to_string());
+    }
+
+    let word_count = contents.split_whitespace().count();
+
+    if SEED_PHRASE_WORD_COUNTS.contains(&word_count) {
+        let phrase = contents.split_whitespace().collect::<Vec<_>>().join(" ");
+
+        // checks every word is in the English wordlist and the checksum matches,
+        // so a typo fails here instead of silently deriving another keypair
+        Mnemonic::validate(&phrase, Language::English).map_err(|err| format!("invalid seed phrase: {err}"))?;
+
+        let seed = generate_seed_from_seed_phrase_and_passphrase(&phrase, passphrase);
+
+        let Some(derivation_path) = derivation_path else {
+            return keypair_from_seed(&seed).map_err(|err| err.to_string());
+        };
+
+        let derivation_path = DerivationPath::from_absolute_path_str(derivation_path)
+            .map_err(|err| format!("invalid derivation path: {err}"))?;
+
+        return keypair_from_seed_and_derivation_path(&seed, Some(derivation_path)).map_err(|err| err.to_string());
+    }
+
+    if word_count == 1 {
+        let bytes = bs58::decode(contents)
+            .into_vec()
+            .map_err(|_| "expected a base58 secret key".to_string())?;
+
+        return Keypair::from_bytes(&bytes).map_err(|err| err.to_string());
+    }
+
+    Err(format!(
+        "expected a JSON byte array, a base58 secret key or a seed phrase of {SEED_PHRASE_WORD_COUNTS:?} words, found {word_count} words"
+    ))
+}
+
+fn expand_tilde(path: &str, home: Option<PathBuf>) -> PathBuf {
+    match (path.strip_prefix('~'), home) {
+        (Some(""), Some(home)) => home,
+        (Some(rest), Some(home)) if rest.starts_with('/') => home.join(&rest[1..]),
+        _ => PathBuf::from(path),
+    }
+}
+
+#[cfg(unix)]
+fn check_permissions(path: &Path) -> Result<(), KeypairError> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let metadata = fs::metadata(path).map_err(|source| KeypairError::Read {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    let mode = metadata.permissions().mode() & 0o777;
+
+    if mode & 0o004 != 0 {
+        return Err(KeypairError::WorldReadable {
+            path: path.to_path_buf(),
+            mode,
+        });
+    }
+
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn check_permissions(_path: &Path) -> Result<(), KeypairError> {
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::signature::Signer;
+    use tempfile::NamedTempFile;
+
+    const SEED_PHRASE: &str =
+        "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+    fn keypair_file(contents: &str) -> NamedTempFile {
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(contents.as_bytes()).unwrap();
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            fs::set_permissions(file.path(), fs::Permissions::from_mode(0o600)).unwrap();
+        }
+
+        file
+    }
+
+    fn read(file: &NamedTempFile, derivation_path: Option<&str>) -> Result<Keypair, KeypairError> {
+        read_keypair_file(file.path().to_str().unwrap(), derivation_path)
+    }
+
+    #[test]
+    fn reads_json_byte_array() {
+        let keypair = Keypair::new();
+        let file = keypair_file(&format!("{:?}", keypair.to_bytes().to_vec()));
+
+        assert_eq!(read(&file, None).unwrap(), keypair);
+    }
+
+    #[test]
+    fn reads_base58_secret_key() {
+        let keypair = Keypair::new();
+        let file = keypair_file(&format!("{}\n", keypair.to_base58_string()));
+
+        assert_eq!(read(&file, None).unwrap(), keypair);
+    }
+
+    #[test]
+    fn reads_seed_phrase() {
+        let file = keypair_file(SEED_PHRASE);
+        let seed = generate_seed_from_seed_phrase_and_passphrase(SEED_PHRASE, "");
+
+        assert_eq!(read(&file, None).unwrap(), keypair_from_seed(&seed).unwrap());
+
+        let derived = read(&file, Some("m/44'/501'/0'/0'")).unwrap();
+        let expected = keypair_from_seed_and_derivation_path(
+            &seed,
+            Some(DerivationPath::from_absolute_path_str("m/44'/501'/0'/0'").unwrap()),
+        )
+        .unwrap();
+
+        assert_eq!(derived.pubkey(), expected.pubkey());
+        assert_ne!(derived.pubkey(), keypair_from_seed(&seed).unwrap().pubkey());
+    }
+
+    #[test]
+    fn reads_seed_phrase_with_passphrase() {
+        let file = keypair_file(SEED_PHRASE);
+        let seed = generate_seed_from_seed_phrase_and_passphrase(SEED_PHRASE, "bootcamp");
+        let path = file.path().to_str().unwrap();
+
+        let keypair = read_keypair_file_with_passphrase(path, None, "bootcamp").unwrap();
+
+        assert_eq!(keypair, keypair_from_seed(&seed).unwrap());
+        assert_ne!(keypair, read(&file, None).unwrap());
+    }
+
+    #[test]
+    fn rejects_invalid_seed_phrase() {
+        // every word is valid but the checksum is not
+        let file = keypair_file(&"abandon ".repeat(12));
+        let err = read(&file, None).unwrap_err();
+        assert!(err.to_string().contains("invalid seed phrase"), "{err}");
+
+        let file = keypair_file(&SEED_PHRASE.replace("about", "abut"));
+        let err = read(&file, None).unwrap_err();
+        assert!(err.to_string().contains("invalid seed phrase"), "{err}");
+    }
+
+    #[test]
+    fn rejects_invalid_contents() {
+        let file = keypair_file("[1, 2, 3]");
+        assert!(matches!(read(&file, None), Err(KeypairError::Parse { .. })));
+
+        let file = keypair_file("[1, 2, 256]");
+        assert!(matches!(read(&file, None), Err(KeypairError::Parse { .. })));
+
+        let file = keypair_file("[1, 2,");
+        assert!(matches!(read(&file, None), Err(KeypairError::Parse { .. })));
+
+        let file = keypair_file("not a keypair");
+        let err = read(&file, None).unwrap_err();
+        assert!(err.to_string().contains(file.path().to_str().unwrap()));
+    }
+
+    #[test]
+    fn missing_file_names_the_path() {
+        let err = read_keypair_file("/does/not/exist.json", None).unwrap_err();
+
+        assert!(matches!(err, KeypairError::Read { .. }));
+        assert!(err.to_string().contains("/does/not/exist.json"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn refuses_world_readable_file() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let file = keypair_file(&Keypair::new().to_base58_string());
+        fs::set_permissions(file.path(), fs::Permissions::from_mode(0o644)).unwrap();
+
+        assert!(matches!(
+            read(&file, None),
+            Err(KeypairError::WorldReadable { mode: 0o644, .. })
+        ));
+    }
+
+    #[test]
+    fn writes_keypair_file_once() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("authority.json");
+        let path = path.to_str().unwrap();
+        let keypair = Keypair::new();
+
+        write_keypair_file(path, &keypair).unwrap();
+        assert_eq!(read_keypair_file(path, None).unwrap(), keypair);
+
+        assert!(matches!(
+            write_keypair_file(path, &Keypair::new()),
+            Err(KeypairError::Write { .. })
+        ));
+        assert_eq!(read_keypair_file(path, None).unwrap(), keypair);
+    }
+
+    #[test]
+    fn expands_tilde() {
+        let home = Some(PathBuf::from("/home/solana"));
+
+        assert_eq!(expand_tilde("~", home.clone()), PathBuf::from("/home/solana"));
+        assert_eq!(
+            expand_tilde("~/.config/solana/id.json", home.clone()),
+            PathBuf::from("/home/solana/.config/solana/id.json")
+        );
+        assert_eq!(expand_tilde("~other/id.json", home.clone()), PathBuf::from("~other/id.json"));
+        assert_eq!(expand_tilde("../payer-keypair.json", home), PathBuf::from("../payer-keypair.json"));
+        assert_eq!(expand_tilde("~/id.json", None), PathBuf::from("~/id.json"));
+    }
+}
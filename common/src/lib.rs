@@ -0,0 +1,22 @@
+//! Helpers shared by the Rust clients of the lesson challenges.
+
+pub mod amount;
+pub mod cluster;
+pub mod keypair;
+pub mod manifest;
+#[cfg(feature = "metadata")]
+pub mod metadata;
+#[cfg(feature = "metadata")]
+pub mod metadata_account;
+pub mod token;
+pub mod transaction;
+
+pub use amount::parse_ui_amount;
+pub use cluster::{Cluster, ClusterArgs};
+pub use keypair::{read_keypair_file, read_keypair_file_with_passphrase, write_keypair_file, KeypairArgs, KeypairError};
+#[cfg(feature = "metadata")]
+pub use metadata::{check_on_chain_fields, MetadataCommand, MetadataError, OffChainMetadata};
+#[cfg(feature = "metadata")]
+pub use metadata_account::{fetch_metadata, MetadataAccountCommand};
+pub use token::{ensure_associated_token_account, AssociatedTokenAccount, TokenProgram};
+pub use transaction::{submit, Context, SimulateArgs, Submitted};
@@ -0,0 +1,244 @@
+use anyhow::{bail, Context as _, Result};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use solana_client::{client_error::ClientError, rpc_client::RpcClient};
+use solana_sdk::{hash::Hash, signature::Signature, transaction::Transaction};
+
+use std::{collections::BTreeMap, fmt, fs, path::Path};
+
+use crate::transaction::{self, Context};
+
+/// What became of the transaction of a manifest row.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Status {
+    /// sent, or about to be, but not confirmed yet
+    Pending,
+    Success,
+    Failed,
+}
+
+/// A row of a manifest: a CSV file recording, for every item of a run sent
+/// over many transactions, the transaction that sent it, so that rerunning
+/// the same command resumes the run without sending any item twice.
+///
+/// Rows are written as pending with their signature and blockhash before
+/// their transaction is sent, and settled once it is confirmed or failed.
+pub trait Row: Serialize + DeserializeOwned {
+    /// Identifies the item of the row within the manifest.
+    type Key: Copy + Ord + fmt::Display;
+
+    fn key(&self) -> Self::Key;
+    fn status(&self) -> Status;
+    fn signature(&self) -> Option<&str>;
+    fn blockhash(&self) -> Option<&str>;
+    fn settle(&mut self, status: Status, error: Option<String>);
+}
+
+/// Rows of a manifest by the key of their item.
+pub type Rows<R> = BTreeMap<<R as Row>::Key, R>;
+
+/// Reads the manifest of a previous run, if any. `check` fails on a row that
+/// does not match the input of this run.
+pub fn read<R: Row>(path: &Path, check: impl Fn(&R) -> Result<()>) -> Result<Rows<R>> {
+    if !path.exists() {
+        return Ok(BTreeMap::new());
+    }
+
+    let mut reader = csv::Reader::from_path(path).with_context(|| format!("Unable to open {}", path.display()))?;
+
+    let mut rows = BTreeMap::new();
+
+    for record in reader.deserialize::<R>() {
+        let row = record.with_context(|| format!("Unable to parse {}", path.display()))?;
+
+        check(&row)?;
+
+        rows.insert(row.key(), row);
+    }
+
+    Ok(rows)
+}
+
+pub fn write<R: Row>(path: &Path, rows: &Rows<R>) -> Result<()> {
+    // written next to the manifest and renamed over it, so an interrupted run
+    // never leaves a truncated file behind
+    let tmp = path.with_extension("csv.tmp");
+
+    let mut writer = csv::Writer::from_path(&tmp)?;
+    for row in rows.values() {
+        writer.serialize(row)?;
+    }
+    writer.flush()?;
+
+    fs::rename(&tmp, path).with_context(|| format!("Unable to write {}", path.display()))
+}
+
+/// Whether the item with `key` is left to send: it has no row yet or its
+/// transaction failed, which changed nothing. Pending rows are settled by
+/// [`resolve_pending`] beforehand.
+pub fn is_unsent<R: Row>(rows: &Rows<R>, key: &R::Key) -> bool {
+    !matches!(rows.get(key), Some(row) if row.status() != Status::Failed)
+}
+
+/// Settles the rows left pending by a previous run from the status of their
+/// transaction, see [`transaction::settle_sent`].
+pub fn resolve_pending<R: Row>(client: &RpcClient, rows: &mut Rows<R>) -> Result<()> {
+    let mut pending: BTreeMap<Signature, Hash> = BTreeMap::new();
+
+    for row in rows.values().filter(|row| row.status() == Status::Pending) {
+        let (Some(signature), Some(blockhash)) = (row.signature(), row.blockhash()) else {
+            bail!("Pending row {} has no signature or blockhash", row.key());
+        };
+
+        pending.insert(signature.parse()?, blockhash.parse()?);
+    }
+
+    for (signature, error) in transaction::settle_sent(client, &pending)? {
+        let status = if error.is_none() { Status::Success } else { Status::Failed };
+        let signature = signature.to_string();
+
+        for row in rows.values_mut() {
+            if row.signature() == Some(signature.as_str()) {
+                row.settle(status, error.clone());
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Submits `transaction`, whose rows were written as pending, with
+/// [`Context::submit_transaction`] and returns what its rows settle to.
+///
+/// A transaction error means the transaction failed as a whole and changed
+/// nothing. Any other error leaves the rows pending, as it may still land,
+/// for the next run to look up.
+pub fn send(context: &Context, transaction: &Transaction) -> (Status, Option<String>) {
+    let signature = transaction.signatures[0];
+
+    match context.submit_transaction(transaction) {
+        Ok(_) => (Status::Success, None),
+        Err(err)
+            if err
+                .downcast_ref::<ClientError>()
+                .is_some_and(|err| err.get_transaction_error().is_some()) =>
+        {
+            eprintln!("Transaction {signature} failed: {err}");
+            (Status::Failed, Some(err.to_string()))
+        }
+        Err(err) => {
+            eprintln!("Transaction {signature} was not confirmed: {err}");
+            (Status::Pending, None)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::NamedTempFile;
+
+    #[derive(Debug, Serialize, Deserialize)]
+    struct TestRow {
+        number: u32,
+        status: Status,
+        signature: Option<String>,
+        blockhash: Option<String>,
+        error: Option<String>,
+    }
+
+    impl Row for TestRow {
+        type Key = u32;
+
+        fn key(&self) -> u32 {
+            self.number
+        }
+
+        fn status(&self) -> Status {
+            self.status
+        }
+
+        fn signature(&self) -> Option<&str> {
+            self.signature.as_deref()
+        }
+
+        fn blockhash(&self) -> Option<&str> {
+            self.blockhash.as_deref()
+        }
+
+        fn settle(&mut self, status: Status, error: Option<String>) {
+            self.status = status;
+            self.error = error;
+        }
+    }
+
+    fn row(number: u32, status: Status) -> TestRow {
+        TestRow {
+            number,
+            status,
+            signature: Some(Signature::new_unique().to_string()),
+            blockhash: Some(Hash::new_unique().to_string()),
+            error: None,
+        }
+    }
+
+    #[test]
+    fn writes_and_reads_rows() {
+        let file = NamedTempFile::new().unwrap();
+        let path = file.path().with_extension("csv");
+
+        assert!(read::<TestRow>(&path, |_| Ok(())).unwrap().is_empty());
+
+        let mut rows = BTreeMap::from([(0, row(0, Status::Success)), (1, row(1, Status::Failed))]);
+        rows.get_mut(&1).unwrap().error = Some("insufficient funds".to_string());
+        write(&path, &rows).unwrap();
+
+        let read_rows: Rows<TestRow> = read(&path, |_| Ok(())).unwrap();
+        assert_eq!(read_rows.len(), 2);
+        assert_eq!(read_rows[&0].status, Status::Success);
+        assert_eq!(read_rows[&0].signature, rows[&0].signature);
+        assert_eq!(read_rows[&0].error, None);
+        assert_eq!(read_rows[&1].error.as_deref(), Some("insufficient funds"));
+
+        let err = read::<TestRow>(&path, |row| match row.number {
+            1 => bail!("row 1 changed"),
+            _ => Ok(()),
+        })
+        .unwrap_err();
+        assert_eq!(err.to_string(), "row 1 changed");
+
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn sends_missing_and_failed_rows_only() {
+        let rows = BTreeMap::from([
+            (0, row(0, Status::Success)),
+            (1, row(1, Status::Pending)),
+            (2, row(2, Status::Failed)),
+        ]);
+
+        assert_eq!((0..4).filter(|key| is_unsent(&rows, key)).collect::<Vec<_>>(), [2, 3]);
+    }
+
+    #[test]
+    fn resolves_pending_rows() {
+        let mut rows = BTreeMap::from([(0, row(0, Status::Success)), (1, row(1, Status::Pending))]);
+
+        // every signature status is a finalized success
+        resolve_pending(&RpcClient::new_mock("succeeds".to_string()), &mut rows).unwrap();
+        assert_eq!(rows[&1].status, Status::Success);
+
+        let mut rows = BTreeMap::from([(0, row(0, Status::Pending))]);
+
+        resolve_pending(&RpcClient::new_mock("instruction_error".to_string()), &mut rows).unwrap();
+        assert_eq!(rows[&0].status, Status::Failed);
+        assert!(rows[&0].error.is_some());
+
+        let mut rows = BTreeMap::from([(0, row(0, Status::Pending))]);
+        rows.get_mut(&0).unwrap().blockhash = None;
+
+        let err = resolve_pending(&RpcClient::new_mock("succeeds".to_string()), &mut rows).unwrap_err();
+        assert!(err.to_string().contains("Pending row 0"), "{err}");
+    }
+}
@@ -0,0 +1,424 @@
+use clap::Subcommand;
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+use thiserror::Error;
+
+/// Longest name the Token Metadata program accepts, in bytes.
+pub const MAX_NAME_LENGTH: usize = 32;
+/// Longest symbol the Token Metadata program accepts, in bytes.
+pub const MAX_SYMBOL_LENGTH: usize = 10;
+/// Longest URI the Token Metadata program accepts, in bytes.
+pub const MAX_URI_LENGTH: usize = 200;
+
+/// URI schemes wallets and marketplaces know how to fetch.
+const URI_SCHEMES: [&str; 4] = ["https", "http", "ipfs", "ar"];
+
+#[derive(Debug, Error)]
+pub enum MetadataError {
+    #[error("Unable to read metadata file {}", path.display())]
+    Read {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+
+    #[error("Unable to parse metadata file {}: {source}", path.display())]
+    Parse {
+        path: PathBuf,
+        #[source]
+        source: serde_json::Error,
+    },
+
+    #[error("Invalid {what}:\n  - {}", problems.join("\n  - "))]
+    Invalid { what: String, problems: Vec<String> },
+}
+
+/// Off-chain metadata JSON of a token or NFT, which the `uri` of its on-chain
+/// metadata points to, following the Metaplex token standard.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct OffChainMetadata {
+    pub name: String,
+    pub symbol: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub description: String,
+    pub image: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub external_url: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub attributes: Vec<Attribute>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub properties: Option<Properties>,
+}
+
+/// A trait of an NFT, shown by marketplaces.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Attribute {
+    pub trait_type: String,
+    /// a string or a number
+    pub value: serde_json::Value,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Properties {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub category: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub files: Vec<File>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub creators: Vec<Creator>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct File {
+    pub uri: String,
+    #[serde(rename = "type")]
+    pub mime_type: String,
+}
+
+/// A creator and its share of the royalties in percent. Parses from a
+/// `<PUBKEY>:<SHARE>` pair.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Creator {
+    pub address: String,
+    pub share: u8,
+}
+
+impl FromStr for Creator {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (address, share) = s
+            .split_once(':')
+            .ok_or_else(|| format!("invalid creator `{s}`, expected <PUBKEY>:<SHARE>"))?;
+
+        Ok(Creator {
+            address: address.to_string(),
+            share: share.parse().map_err(|_| format!("invalid share `{share}`"))?,
+        })
+    }
+}
+
+impl OffChainMetadata {
+    /// Checks the metadata against the Metaplex standard and the limits of the
+    /// on-chain fields it is copied to, reporting every problem at once.
+    pub fn validate(&self) -> Result<(), MetadataError> {
+        let mut problems = on_chain_problems(&self.name, &self.symbol, None);
+
+        if let Err(problem) = check_uri("image", &self.image) {
+            problems.push(problem);
+        }
+        if let Some(external_url) = &self.external_url {
+            problems.extend(check_uri("external_url", external_url).err());
+        }
+
+        for attribute in &self.attributes {
+            if attribute.trait_type.trim().is_empty() {
+                problems.push("attributes: trait_type can't be empty".to_string());
+            }
+            if !(attribute.value.is_string() || attribute.value.is_number()) {
+                problems.push(format!(
+                    "attributes: value of `{}` must be a string or a number",
+                    attribute.trait_type
+                ));
+            }
+        }
+
+        if let Some(properties) = &self.properties {
+            for file in &properties.files {
+                problems.extend(check_uri("properties.files", &file.uri).err());
+
+                if !file.mime_type.contains('/') {
+                    problems.push(format!("properties.files: invalid type `{}` of {}", file.mime_type, file.uri));
+                }
+            }
+
+            if !properties.creators.is_empty() {
+                for creator in &properties.creators {
+                    if Pubkey::from_str(&creator.address).is_err() {
+                        problems.push(format!("properties.creators: invalid address `{}`", creator.address));
+                    }
+                }
+
+                let total: u32 = properties.creators.iter().map(|creator| u32::from(creator.share)).sum();
+                if total != 100 {
+                    problems.push(format!("properties.creators: shares add up to {total}%, not 100%"));
+                }
+            }
+        }
+
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(MetadataError::Invalid {
+                what: "metadata".to_string(),
+                problems,
+            })
+        }
+    }
+
+    /// Validates the metadata and renders it as pretty printed JSON.
+    pub fn to_json(&self) -> Result<String, MetadataError> {
+        self.validate()?;
+
+        Ok(serde_json::to_string_pretty(self).expect("metadata always serializes"))
+    }
+}
+
+/// Reads and validates the metadata JSON file at `path`.
+pub fn read_metadata_file(path: &Path) -> Result<OffChainMetadata, MetadataError> {
+    let contents = fs::read_to_string(path).map_err(|source| MetadataError::Read {
+        path: path.to_path_buf(),
+        source,
+    })?;
+
+    let metadata: OffChainMetadata = serde_json::from_str(&contents).map_err(|source| MetadataError::Parse {
+        path: path.to_path_buf(),
+        source,
+    })?;
+
+    metadata.validate().map_err(|err| match err {
+        MetadataError::Invalid { problems, .. } => MetadataError::Invalid {
+            what: format!("metadata file {}", path.display()),
+            problems,
+        },
+        err => err,
+    })?;
+
+    Ok(metadata)
+}
+
+/// Checks the name, symbol and URI about to be written to an on-chain
+/// metadata account, so that input the Token Metadata program would reject
+/// fails before anything is sent.
+pub fn check_on_chain_fields(name: &str, symbol: &str, uri: &str) -> Result<(), MetadataError> {
+    let problems = on_chain_problems(name, symbol, Some(uri));
+
+    if problems.is_empty() {
+        Ok(())
+    } else {
+        Err(MetadataError::Invalid {
+            what: "on-chain metadata".to_string(),
+            problems,
+        })
+    }
+}
+
+fn on_chain_problems(name: &str, symbol: &str, uri: Option<&str>) -> Vec<String> {
+    let mut problems = Vec::new();
+
+    if name.trim().is_empty() {
+        problems.push("name can't be empty".to_string());
+    }
+
+    for (field, value, max) in [("name", name, MAX_NAME_LENGTH), ("symbol", symbol, MAX_SYMBOL_LENGTH)]
+        .into_iter()
+        .chain(uri.map(|uri| ("uri", uri, MAX_URI_LENGTH)))
+    {
+        if value.len() > max {
+            problems.push(format!("{field} is {} bytes long, at most {max} are allowed", value.len()));
+        }
+    }
+
+    if let Some(uri) = uri {
+        problems.extend(check_uri("uri", uri).err());
+    }
+
+    problems
+}
+
+fn check_uri(field: &str, uri: &str) -> Result<(), String> {
+    match uri.split_once("://") {
+        Some((scheme, rest)) if URI_SCHEMES.contains(&scheme) && !rest.is_empty() => Ok(()),
+        _ => Err(format!(
+            "{field}: `{uri}` is not a URI with one of the {URI_SCHEMES:?} schemes"
+        )),
+    }
+}
+
+/// Subcommands building and checking metadata JSON files, shared by the clients
+/// minting tokens.
+#[derive(Subcommand)]
+pub enum MetadataCommand {
+    /// Write a metadata JSON file following the Metaplex token standard
+    NewMetadata {
+        /// Where to write the JSON file
+        out: PathBuf,
+
+        #[arg(long)]
+        name: String,
+
+        #[arg(long)]
+        symbol: String,
+
+        #[arg(long, default_value = "")]
+        description: String,
+
+        /// URI of the image
+        #[arg(long)]
+        image: String,
+
+        /// MIME type of the image, listed in `properties.files`
+        #[arg(long, default_value = "image/png")]
+        image_type: String,
+
+        #[arg(long)]
+        external_url: Option<String>,
+
+        /// A trait as <TRAIT>=<VALUE>; may be repeated
+        #[arg(long = "attribute", value_name = "TRAIT=VALUE", value_parser = parse_attribute)]
+        attributes: Vec<Attribute>,
+
+        /// A creator as <PUBKEY>:<SHARE>, shares in percent adding up to 100; may be repeated
+        #[arg(long = "creator", value_name = "PUBKEY:SHARE")]
+        creators: Vec<Creator>,
+    },
+
+    /// Check metadata JSON files against the Metaplex token standard
+    ValidateMetadata {
+        #[arg(required = true)]
+        files: Vec<PathBuf>,
+    },
+}
+
+fn parse_attribute(s: &str) -> Result<Attribute, String> {
+    let (trait_type, value) = s
+        .split_once('=')
+        .ok_or_else(|| format!("invalid attribute `{s}`, expected <TRAIT>=<VALUE>"))?;
+
+    // numbers stay numbers so marketplaces can sort and filter on them
+    let value = serde_json::from_str::<serde_json::Number>(value)
+        .map(serde_json::Value::Number)
+        .unwrap_or_else(|_| serde_json::Value::String(value.to_string()));
+
+    Ok(Attribute {
+        trait_type: trait_type.to_string(),
+        value,
+    })
+}
+
+impl MetadataCommand {
+    pub fn run(self) -> anyhow::Result<()> {
+        match self {
+            MetadataCommand::NewMetadata {
+                out,
+                name,
+                symbol,
+                description,
+                image,
+                image_type,
+                external_url,
+                attributes,
+                creators,
+            } => {
+                let metadata = OffChainMetadata {
+                    name,
+                    symbol,
+                    description,
+                    external_url,
+                    attributes,
+                    properties: Some(Properties {
+                        category: Some("image".to_string()),
+                        files: vec![File {
+                            uri: image.clone(),
+                            mime_type: image_type,
+                        }],
+                        creators,
+                    }),
+                    image,
+                };
+
+                fs::write(&out, metadata.to_json()? + "\n")?;
+                println!("metadata written to {}, upload it and pass its URI with --uri", out.display());
+            }
+            MetadataCommand::ValidateMetadata { files } => {
+                let mut invalid = 0;
+
+                for file in &files {
+                    match read_metadata_file(file) {
+                        Ok(_) => println!("{}: ok", file.display()),
+                        Err(err) => {
+                            invalid += 1;
+                            eprintln!("{err}");
+                        }
+                    }
+                }
+
+                if invalid > 0 {
+                    anyhow::bail!("{invalid} of {} metadata files are invalid", files.len());
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn metadata() -> OffChainMetadata {
+        OffChainMetadata {
+            name: "Bootcamp #1".to_string(),
+            symbol: "BOOT".to_string(),
+            image: "https://example.com/1.png".to_string(),
+            attributes: vec![parse_attribute("Background=Blue").unwrap(), parse_attribute("Level=3").unwrap()],
+            properties: Some(Properties {
+                files: vec![File {
+                    uri: "https://example.com/1.png".to_string(),
+                    mime_type: "image/png".to_string(),
+                }],
+                creators: vec!["63EEC9FfGyksm7PkVC6z8uAmqozbQcTzbkWJNsgqjkFs:100".parse().unwrap()],
+                ..Properties::default()
+            }),
+            ..OffChainMetadata::default()
+        }
+    }
+
+    #[test]
+    fn builds_and_parses_metadata_json() {
+        let json = metadata().to_json().unwrap();
+
+        assert!(json.contains(r#""trait_type": "Level",
+      "value": 3"#));
+        assert!(json.contains(r#""type": "image/png""#));
+        assert!(!json.contains("description"));
+        assert_eq!(serde_json::from_str::<OffChainMetadata>(&json).unwrap(), metadata());
+    }
+
+    #[test]
+    fn repo_assets_are_valid() {
+        for asset in ["../assets/tt-token.json", "../assets/sb-fptu-token.json"] {
+            let path = Path::new(env!("CARGO_MANIFEST_DIR")).join(asset);
+
+            read_metadata_file(&path).unwrap();
+        }
+    }
+
+    #[test]
+    fn reports_every_problem() {
+        let mut metadata = metadata();
+        metadata.name = "A name much longer than thirty-two bytes".to_string();
+        metadata.image = "example.com/1.png".to_string();
+        metadata.properties.as_mut().unwrap().creators[0].share = 90;
+
+        let Err(MetadataError::Invalid { problems, .. }) = metadata.validate() else {
+            panic!("expected the metadata to be invalid");
+        };
+
+        assert_eq!(problems.len(), 3, "{problems:?}");
+    }
+
+    #[test]
+    fn checks_on_chain_limits() {
+        assert!(check_on_chain_fields("Test Token", "TT", "https://example.com/tt.json").is_ok());
+        assert!(check_on_chain_fields("Test Token", "TOOLONGSYMBOL", "https://example.com/tt.json").is_err());
+        assert!(check_on_chain_fields("", "TT", "https://example.com/tt.json").is_err());
+        assert!(check_on_chain_fields("Test Token", "TT", &format!("https://{}", "a".repeat(200))).is_err());
+    }
+}
@@ -0,0 +1,265 @@
+use anyhow::{anyhow, bail, Result};
+use clap::Subcommand;
+use mpl_token_metadata::accounts::Metadata;
+use mpl_token_metadata::instructions::{UpdateMetadataAccountV2, UpdateMetadataAccountV2InstructionArgs};
+use mpl_token_metadata::types::DataV2;
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{pubkey::Pubkey, signer::Signer};
+
+use crate::{metadata, Context};
+
+/// Fetches and decodes the Metaplex metadata account of `mint`.
+pub fn fetch_metadata(client: &RpcClient, mint: &Pubkey) -> Result<Metadata> {
+    let (address, _) = Metadata::find_pda(mint);
+    let account = client
+        .get_account(&address)
+        .map_err(|err| anyhow!("Unable to fetch the metadata of mint {}: {}", mint, err))?;
+
+    Metadata::from_bytes(&account.data).map_err(|err| anyhow!("Invalid metadata account {}: {}", address, err))
+}
+
+/// Subcommands changing the metadata account of an existing mint, shared by the
+/// clients minting tokens. The update authority signs, and must be the payer
+/// unless `--authority` is given.
+#[derive(Subcommand)]
+pub enum MetadataAccountCommand {
+    /// Change the name, symbol, URI or royalty of a mint's metadata
+    UpdateMetadata {
+        mint: Pubkey,
+
+        #[arg(long)]
+        name: Option<String>,
+
+        #[arg(long)]
+        symbol: Option<String>,
+
+        /// URI of the off-chain metadata JSON
+        #[arg(long)]
+        uri: Option<String>,
+
+        /// Royalty of secondary sales in basis points, e.g. 500 for 5%
+        #[arg(long, value_parser = clap::value_parser!(u16).range(0..=10_000))]
+        royalty_bps: Option<u16>,
+
+        /// Keypair file of the update authority [default: the payer]
+        #[arg(long, value_name = "KEYPAIR")]
+        authority: Option<String>,
+    },
+
+    /// Hand the update authority of a mint's metadata over to another account
+    SetUpdateAuthority {
+        mint: Pubkey,
+
+        new_authority: Pubkey,
+
+        /// Keypair file of the current update authority [default: the payer]
+        #[arg(long, value_name = "KEYPAIR")]
+        authority: Option<String>,
+    },
+
+    /// Freeze a mint's metadata for good, e.g. once a collection is final
+    MakeImmutable {
+        mint: Pubkey,
+
+        /// Keypair file of the update authority [default: the payer]
+        #[arg(long, value_name = "KEYPAIR")]
+        authority: Option<String>,
+    },
+}
+
+/// Fields of a metadata account its update authority can change.
+#[derive(Clone, Debug, PartialEq)]
+struct Fields {
+    name: String,
+    symbol: String,
+    uri: String,
+    seller_fee_basis_points: u16,
+    update_authority: Pubkey,
+    is_mutable: bool,
+}
+
+impl From<&Metadata> for Fields {
+    fn from(metadata: &Metadata) -> Self {
+        // the program pads strings to their maximum length with null bytes
+        let trim = |value: &str| value.trim_end_matches('\0').to_string();
+
+        Fields {
+            name: trim(&metadata.name),
+            symbol: trim(&metadata.symbol),
+            uri: trim(&metadata.uri),
+            seller_fee_basis_points: metadata.seller_fee_basis_points,
+            update_authority: metadata.update_authority,
+            is_mutable: metadata.is_mutable,
+        }
+    }
+}
+
+impl Fields {
+    fn data_changed(&self, other: &Fields) -> bool {
+        (&self.name, &self.symbol, &self.uri, self.seller_fee_basis_points)
+            != (&other.name, &other.symbol, &other.uri, other.seller_fee_basis_points)
+    }
+}
+
+/// Lines describing every field that differs between `before` and `after`.
+fn diff(before: &Fields, after: &Fields) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut push = |field: &str, before: String, after: String| {
+        if before != after {
+            lines.push(format!("{field}: {before} -> {after}"));
+        }
+    };
+
+    push("name", format!("{:?}", before.name), format!("{:?}", after.name));
+    push("symbol", format!("{:?}", before.symbol), format!("{:?}", after.symbol));
+    push("uri", format!("{:?}", before.uri), format!("{:?}", after.uri));
+    push(
+        "royalty",
+        format!("{} bps", before.seller_fee_basis_points),
+        format!("{} bps", after.seller_fee_basis_points),
+    );
+    push(
+        "update authority",
+        before.update_authority.to_string(),
+        after.update_authority.to_string(),
+    );
+    push("mutable", before.is_mutable.to_string(), after.is_mutable.to_string());
+
+    lines
+}
+
+impl MetadataAccountCommand {
+    pub fn run(self, context: &Context) -> Result<()> {
+        let (mint, authority) = match &self {
+            MetadataAccountCommand::UpdateMetadata { mint, authority, .. }
+            | MetadataAccountCommand::SetUpdateAuthority { mint, authority, .. }
+            | MetadataAccountCommand::MakeImmutable { mint, authority } => (*mint, authority.as_deref()),
+        };
+        let authority = authority.map(|path| crate::read_keypair_file(path, None)).transpose()?;
+        let authority = authority.as_ref().unwrap_or(context.payer);
+
+        let metadata = fetch_metadata(context.client, &mint)?;
+        let before = Fields::from(&metadata);
+
+        if !before.is_mutable {
+            bail!("The metadata of mint {} is immutable", mint);
+        }
+        if before.update_authority != authority.pubkey() {
+            bail!(
+                "{} is not the update authority of mint {}, {} is",
+                authority.pubkey(),
+                mint,
+                before.update_authority
+            );
+        }
+
+        let mut after = before.clone();
+        match self {
+            MetadataAccountCommand::UpdateMetadata {
+                name,
+                symbol,
+                uri,
+                royalty_bps,
+                ..
+            } => {
+                after.name = name.unwrap_or(after.name);
+                after.symbol = symbol.unwrap_or(after.symbol);
+                after.uri = uri.unwrap_or(after.uri);
+                after.seller_fee_basis_points = royalty_bps.unwrap_or(after.seller_fee_basis_points);
+
+                metadata::check_on_chain_fields(&after.name, &after.symbol, &after.uri)?;
+            }
+            MetadataAccountCommand::SetUpdateAuthority { new_authority, .. } => after.update_authority = new_authority,
+            MetadataAccountCommand::MakeImmutable { .. } => after.is_mutable = false,
+        }
+
+        let changes = diff(&before, &after);
+        if changes.is_empty() {
+            println!("The metadata of mint {} already matches, nothing to update", mint);
+            return Ok(());
+        }
+
+        println!("metadata of mint {} ({}):", mint, Metadata::find_pda(&mint).0);
+        for change in &changes {
+            println!("  {change}");
+        }
+        if !after.is_mutable {
+            println!("once sent, this metadata can never be changed again");
+        }
+
+        // creators, collection and uses are carried over as they are: the
+        // program refuses to change their verified flags in an update
+        let data = before.data_changed(&after).then(|| DataV2 {
+            name: after.name.clone(),
+            symbol: after.symbol.clone(),
+            uri: after.uri.clone(),
+            seller_fee_basis_points: after.seller_fee_basis_points,
+            creators: metadata.creators.clone(),
+            collection: metadata.collection.clone(),
+            uses: metadata.uses.clone(),
+        });
+
+        let instruction = UpdateMetadataAccountV2 {
+            metadata: Metadata::find_pda(&mint).0,
+            update_authority: authority.pubkey(),
+        }
+        .instruction(UpdateMetadataAccountV2InstructionArgs {
+            data,
+            new_update_authority: (after.update_authority != before.update_authority).then_some(after.update_authority),
+            primary_sale_happened: None,
+            is_mutable: (after.is_mutable != before.is_mutable).then_some(after.is_mutable),
+        });
+
+        context.send_transaction(&[authority], &[instruction])?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fields() -> Fields {
+        Fields {
+            name: "Test Token".to_string(),
+            symbol: "TT".to_string(),
+            uri: "https://example.com/tt.json".to_string(),
+            seller_fee_basis_points: 0,
+            update_authority: Pubkey::new_unique(),
+            is_mutable: true,
+        }
+    }
+
+    #[test]
+    fn diff_lists_changed_fields_only() {
+        let before = fields();
+        let mut after = before.clone();
+
+        assert!(diff(&before, &after).is_empty());
+
+        after.name = "Bootcamp Token".to_string();
+        after.seller_fee_basis_points = 500;
+        after.is_mutable = false;
+
+        assert_eq!(
+            diff(&before, &after),
+            [
+                r#"name: "Test Token" -> "Bootcamp Token""#,
+                "royalty: 0 bps -> 500 bps",
+                "mutable: true -> false",
+            ]
+        );
+        assert!(before.data_changed(&after));
+    }
+
+    #[test]
+    fn authority_and_mutability_leave_data_alone() {
+        let before = fields();
+        let mut after = before.clone();
+        after.update_authority = Pubkey::new_unique();
+        after.is_mutable = false;
+
+        assert!(!before.data_changed(&after));
+    }
+}
@@ -0,0 +1,138 @@
+use anyhow::{bail, Result};
+use clap::ValueEnum;
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{instruction::Instruction, pubkey::Pubkey};
+use spl_associated_token_account::get_associated_token_address_with_program_id;
+use spl_associated_token_account::instruction::create_associated_token_account_idempotent;
+
+/// Token program owning a mint and its token accounts.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum TokenProgram {
+    /// The original SPL Token program
+    #[default]
+    Token,
+    /// SPL Token-2022, which supports mint extensions like transfer fees or embedded metadata
+    #[value(name = "token-2022")]
+    Token2022,
+}
+
+impl TokenProgram {
+    pub fn id(self) -> Pubkey {
+        match self {
+            TokenProgram::Token => spl_token::id(),
+            TokenProgram::Token2022 => spl_token_2022::id(),
+        }
+    }
+
+    /// Token program of an account owned by `owner`, if it is one of them.
+    pub fn from_owner(owner: &Pubkey) -> Option<TokenProgram> {
+        [TokenProgram::Token, TokenProgram::Token2022]
+            .into_iter()
+            .find(|program| program.id() == *owner)
+    }
+}
+
+/// Associated token account of an owner for a mint, as returned by
+/// [`ensure_associated_token_account`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct AssociatedTokenAccount {
+    pub address: Pubkey,
+    /// Instruction creating the account, paid by the payer, when it doesn't
+    /// exist yet.
+    pub create: Option<Instruction>,
+}
+
+/// Looks up the associated token account of `owner` for `mint` under the token
+/// program `program_id`, and builds the instruction creating it if it is missing.
+///
+/// RPC errors are returned instead of being taken for a missing account. The
+/// instruction is the idempotent one, so the transaction still goes through if
+/// someone else creates the account first.
+pub fn ensure_associated_token_account(
+    client: &RpcClient,
+    payer: &Pubkey,
+    owner: &Pubkey,
+    mint: &Pubkey,
+    program_id: &Pubkey,
+) -> Result<AssociatedTokenAccount> {
+    let address = get_associated_token_address_with_program_id(owner, mint, program_id);
+
+    let create = match client.get_account_with_commitment(&address, client.commitment())?.value {
+        Some(account) if account.owner == *program_id => None,
+        Some(account) => bail!(
+            "Associated token account {} is owned by {}, not by token program {}",
+            address,
+            account.owner,
+            program_id
+        ),
+        None => Some(create_associated_token_account_idempotent(payer, owner, mint, program_id)),
+    };
+
+    Ok(AssociatedTokenAccount { address, create })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_client::rpc_request::RpcRequest;
+    use std::collections::HashMap;
+
+    fn account_info(owner: &Pubkey) -> serde_json::Value {
+        serde_json::json!({
+            "context": { "slot": 1 },
+            "value": {
+                "lamports": 2_039_280,
+                "data": ["", "base64"],
+                "owner": owner.to_string(),
+                "executable": false,
+                "rentEpoch": 0,
+            },
+        })
+    }
+
+    #[test]
+    fn ensures_associated_token_account() {
+        let (payer, mint) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let program_id = TokenProgram::Token2022.id();
+        let address = get_associated_token_address_with_program_id(&payer, &mint, &program_id);
+
+        // the mock answers every account lookup with a missing account
+        let client = RpcClient::new_mock("succeeds".to_string());
+        let missing = ensure_associated_token_account(&client, &payer, &payer, &mint, &program_id).unwrap();
+        assert_eq!(missing.address, address);
+        assert_eq!(
+            missing.create,
+            Some(create_associated_token_account_idempotent(&payer, &payer, &mint, &program_id))
+        );
+
+        let client = RpcClient::new_mock_with_mocks(
+            "succeeds".to_string(),
+            HashMap::from([(RpcRequest::GetAccountInfo, account_info(&program_id))]),
+        );
+        let existing = ensure_associated_token_account(&client, &payer, &payer, &mint, &program_id).unwrap();
+        assert_eq!(existing, AssociatedTokenAccount { address, create: None });
+    }
+
+    #[test]
+    fn ensure_associated_token_account_reports_errors() {
+        let (payer, mint) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let program_id = TokenProgram::Token.id();
+
+        // answers every request with `null`, which is not a valid response
+        let failing = RpcClient::new_mock("fails".to_string());
+        assert!(ensure_associated_token_account(&failing, &payer, &payer, &mint, &program_id).is_err());
+
+        let client = RpcClient::new_mock_with_mocks(
+            "succeeds".to_string(),
+            HashMap::from([(RpcRequest::GetAccountInfo, account_info(&solana_sdk::system_program::id()))]),
+        );
+        assert!(ensure_associated_token_account(&client, &payer, &payer, &mint, &program_id).is_err());
+    }
+
+    #[test]
+    fn token_program_from_owner() {
+        assert_eq!(TokenProgram::from_owner(&spl_token::id()), Some(TokenProgram::Token));
+        assert_eq!(TokenProgram::from_owner(&spl_token_2022::id()), Some(TokenProgram::Token2022));
+        assert_eq!(TokenProgram::from_owner(&solana_sdk::system_program::id()), None);
+    }
+}
@@ -0,0 +1,369 @@
+use anyhow::{bail, Result};
+use clap::Args;
+use solana_client::{
+    rpc_client::RpcClient,
+    rpc_config::{RpcSimulateTransactionAccountsConfig, RpcSimulateTransactionConfig},
+};
+use solana_sdk::{
+    commitment_config::CommitmentConfig,
+    hash::Hash,
+    instruction::Instruction,
+    native_token::lamports_to_sol,
+    pubkey::Pubkey,
+    signature::{Keypair, Signature, Signer},
+    transaction::Transaction,
+};
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::Cluster;
+
+/// `getSignatureStatuses` accepts at most 256 signatures per request.
+const MAX_SIGNATURE_STATUSES: usize = 256;
+
+/// What became of sent transactions: `None` for a success, the reason of the
+/// failure otherwise.
+pub type Outcomes = BTreeMap<Signature, Option<String>>;
+
+/// `--simulate` flag shared by the clients.
+#[derive(Args, Clone, Copy, Debug)]
+pub struct SimulateArgs {
+    /// Simulate the transactions instead of sending them, printing their logs, compute units and balance changes
+    #[arg(long, global = true)]
+    pub simulate: bool,
+}
+
+/// What became of a transaction given to [`submit`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Submitted {
+    /// Sent and confirmed on the cluster.
+    Sent(Signature),
+    /// Only simulated, nothing was written on the cluster.
+    Simulated,
+}
+
+/// Cluster, fee payer and mode a client sends its transactions with.
+pub struct Context<'a> {
+    pub client: &'a RpcClient,
+    pub cluster: &'a Cluster,
+    pub payer: &'a Keypair,
+    pub simulate: bool,
+}
+
+impl Context<'_> {
+    /// Signs `instructions` with the payer and `signers`, which may include the
+    /// payer again, then submits them as a single transaction and prints its
+    /// explorer link once sent.
+    pub fn send_transaction(&self, signers: &[&Keypair], instructions: &[Instruction]) -> Result<Submitted> {
+        let transaction = self.sign_transaction(signers, instructions)?;
+
+        self.submit_transaction(&transaction)
+    }
+
+    /// Signs `instructions` with the payer and `signers`, which may include the
+    /// payer again, on the latest blockhash. For callers that need the
+    /// signature before [`Context::submit_transaction`].
+    pub fn sign_transaction(&self, signers: &[&Keypair], instructions: &[Instruction]) -> Result<Transaction> {
+        let mut all_signers = vec![self.payer];
+        for signer in signers {
+            if !all_signers.iter().any(|known| known.pubkey() == signer.pubkey()) {
+                all_signers.push(signer);
+            }
+        }
+
+        let blockhash = self.client.get_latest_blockhash()?;
+
+        Ok(Transaction::new_signed_with_payer(
+            instructions,
+            Some(&self.payer.pubkey()),
+            &all_signers,
+            blockhash,
+        ))
+    }
+
+    /// Submits `transaction` with [`submit`] and prints its explorer link once
+    /// sent.
+    pub fn submit_transaction(&self, transaction: &Transaction) -> Result<Submitted> {
+        let submitted = submit(self.client, transaction, self.simulate)?;
+
+        if let Submitted::Sent(signature) = submitted {
+            println!("explorer url: {}", self.cluster.tx_explorer_url(&signature.to_string()));
+        }
+
+        Ok(submitted)
+    }
+}
+
+/// Sends and confirms `transaction`, or only simulates it with [`simulate`]
+/// when `simulate` is set.
+pub fn submit(client: &RpcClient, transaction: &Transaction, simulate: bool) -> Result<Submitted> {
+    if simulate {
+        self::simulate(client, transaction)?;
+
+        return Ok(Submitted::Simulated);
+    }
+
+    Ok(Submitted::Sent(client.send_and_confirm_transaction(transaction)?))
+}
+
+/// Looks up what became of transactions sent by an earlier run, keyed by their
+/// signature and the blockhash they were signed with. Only statuses that
+/// reached `confirmed` count.
+///
+/// A transaction that is not settled yet can only be given up once its
+/// blockhash has expired, as it could still land before that, so this fails
+/// until then. The expiry is checked before looking the transaction up again,
+/// so one landing in between is not mistaken for an expired one.
+pub fn settle_sent(
+    client: &RpcClient,
+    sent: &BTreeMap<Signature, Hash>,
+) -> Result<Outcomes> {
+    let signatures: Vec<Signature> = sent.keys().copied().collect();
+    let (mut outcomes, unsettled) = settled_statuses(client, &signatures)?;
+
+    let mut expired_blockhashes = BTreeSet::new();
+
+    for signature in &unsettled {
+        let blockhash = sent[signature];
+
+        if !expired_blockhashes.contains(&blockhash) {
+            if client.is_blockhash_valid(&blockhash, CommitmentConfig::processed())? {
+                bail!("Transaction {signature} is not confirmed yet but may still land, rerun in a minute");
+            }
+
+            expired_blockhashes.insert(blockhash);
+        }
+    }
+
+    // every blockhash left has expired, so these statuses can't change anymore
+    let (expired_outcomes, expired) = settled_statuses(client, &unsettled)?;
+    outcomes.extend(expired_outcomes);
+
+    for signature in expired {
+        outcomes.insert(signature, Some("transaction expired".to_string()));
+    }
+
+    Ok(outcomes)
+}
+
+/// Outcomes of the `signatures` confirmed on the cluster, and the signatures
+/// that were not found or are only processed.
+fn settled_statuses(
+    client: &RpcClient,
+    signatures: &[Signature],
+) -> Result<(Outcomes, Vec<Signature>)> {
+    let mut outcomes = BTreeMap::new();
+    let mut unsettled = Vec::new();
+
+    for chunk in signatures.chunks(MAX_SIGNATURE_STATUSES) {
+        let statuses = client.get_signature_statuses_with_history(chunk)?.value;
+
+        for (signature, status) in chunk.iter().zip(statuses) {
+            match status {
+                Some(status) if status.satisfies_commitment(CommitmentConfig::confirmed()) => {
+                    outcomes.insert(*signature, status.err.map(|err| err.to_string()));
+                }
+                _ => unsettled.push(*signature),
+            }
+        }
+    }
+
+    Ok((outcomes, unsettled))
+}
+
+/// Simulates the signed `transaction` and prints its logs, the compute units it
+/// consumed and how the balance of every account it touches would change.
+///
+/// Fails if the simulated transaction fails, so that scripts and CI jobs
+/// running the clients against a test validator exit non-zero.
+pub fn simulate(client: &RpcClient, transaction: &Transaction) -> Result<()> {
+    let keys = &transaction.message.account_keys;
+
+    let before: Vec<u64> = client
+        .get_multiple_accounts(keys)?
+        .iter()
+        .map(|account| account.as_ref().map_or(0, |account| account.lamports))
+        .collect();
+
+    let result = client
+        .simulate_transaction_with_config(
+            transaction,
+            RpcSimulateTransactionConfig {
+                sig_verify: true,
+                commitment: Some(client.commitment()),
+                accounts: Some(RpcSimulateTransactionAccountsConfig {
+                    encoding: None,
+                    addresses: keys.iter().map(Pubkey::to_string).collect(),
+                }),
+                ..RpcSimulateTransactionConfig::default()
+            },
+        )?
+        .value;
+
+    println!("Simulation logs:");
+    for log in result.logs.unwrap_or_default() {
+        println!("  {log}");
+    }
+
+    if let Some(units) = result.units_consumed {
+        println!("Compute units consumed: {units}");
+    }
+
+    if let Some(err) = result.err {
+        bail!("Simulated transaction failed: {err}");
+    }
+
+    let after: Vec<u64> = result
+        .accounts
+        .unwrap_or_default()
+        .iter()
+        .map(|account| account.as_ref().map_or(0, |account| account.lamports))
+        .collect();
+
+    println!("Balance changes:");
+    for change in balance_changes(keys, &before, &after) {
+        println!(
+            "  {}: {} -> {} SOL ({:+} lamports)",
+            change.pubkey,
+            lamports_to_sol(change.before),
+            lamports_to_sol(change.after),
+            change.delta()
+        );
+    }
+
+    Ok(())
+}
+
+/// Lamports held by an account before and after a simulated transaction.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct BalanceChange {
+    pubkey: Pubkey,
+    before: u64,
+    after: u64,
+}
+
+impl BalanceChange {
+    fn delta(&self) -> i128 {
+        i128::from(self.after) - i128::from(self.before)
+    }
+}
+
+/// Pairs up the balances of `keys`, leaving out the accounts left untouched.
+fn balance_changes(keys: &[Pubkey], before: &[u64], after: &[u64]) -> Vec<BalanceChange> {
+    keys.iter()
+        .zip(before.iter().zip(after))
+        .filter(|(_, (before, after))| before != after)
+        .map(|(pubkey, (before, after))| BalanceChange {
+            pubkey: *pubkey,
+            before: *before,
+            after: *after,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_client::rpc_request::RpcRequest;
+    use std::collections::HashMap;
+
+    fn response(value: serde_json::Value) -> serde_json::Value {
+        serde_json::json!({ "context": { "slot": 1 }, "value": value })
+    }
+
+    fn status(confirmation_status: &str) -> serde_json::Value {
+        serde_json::json!({
+            "slot": 1,
+            "confirmations": 0,
+            "status": { "Ok": null },
+            "err": null,
+            "confirmationStatus": confirmation_status,
+        })
+    }
+
+    fn sent() -> (Signature, BTreeMap<Signature, Hash>) {
+        let signature = Signature::new_unique();
+
+        (signature, BTreeMap::from([(signature, Hash::new_unique())]))
+    }
+
+    #[test]
+    fn settles_transaction_landing_after_first_lookup() {
+        let (signature, sent) = sent();
+
+        // mocks answer once, the second status lookup gets the default
+        // finalized success
+        let client = RpcClient::new_mock_with_mocks(
+            "succeeds".to_string(),
+            HashMap::from([
+                (RpcRequest::GetSignatureStatuses, response(serde_json::json!([null]))),
+                (RpcRequest::IsBlockhashValid, response(serde_json::json!(false))),
+            ]),
+        );
+
+        assert_eq!(settle_sent(&client, &sent).unwrap(), BTreeMap::from([(signature, None)]));
+    }
+
+    #[test]
+    fn settles_expired_transaction() {
+        let (signature, sent) = sent();
+
+        let client = RpcClient::new_mock_with_mocks(
+            "sig_not_found".to_string(),
+            HashMap::from([(RpcRequest::IsBlockhashValid, response(serde_json::json!(false)))]),
+        );
+
+        assert_eq!(
+            settle_sent(&client, &sent).unwrap(),
+            BTreeMap::from([(signature, Some("transaction expired".to_string()))])
+        );
+    }
+
+    #[test]
+    fn waits_for_unconfirmed_transaction_with_valid_blockhash() {
+        let (_, sent) = sent();
+
+        let client = RpcClient::new_mock_with_mocks(
+            "sig_not_found".to_string(),
+            HashMap::from([(RpcRequest::IsBlockhashValid, response(serde_json::json!(true)))]),
+        );
+        assert!(settle_sent(&client, &sent).is_err());
+
+        // only processed, it may still be dropped with its fork
+        let client = RpcClient::new_mock_with_mocks(
+            "succeeds".to_string(),
+            HashMap::from([
+                (RpcRequest::GetSignatureStatuses, response(serde_json::json!([status("processed")]))),
+                (RpcRequest::IsBlockhashValid, response(serde_json::json!(true))),
+            ]),
+        );
+        assert!(settle_sent(&client, &sent).is_err());
+    }
+
+    #[test]
+    fn settles_failed_transaction() {
+        let (signature, sent) = sent();
+
+        let client = RpcClient::new_mock("instruction_error".to_string());
+
+        let outcomes = settle_sent(&client, &sent).unwrap();
+        assert!(outcomes[&signature].is_some());
+    }
+
+    #[test]
+    fn balance_changes_skip_untouched_accounts() {
+        let keys = [Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique()];
+
+        let changes = balance_changes(&keys, &[10_000, 500, 0], &[4_000, 500, 1_000]);
+
+        assert_eq!(
+            changes,
+            vec![
+                BalanceChange { pubkey: keys[0], before: 10_000, after: 4_000 },
+                BalanceChange { pubkey: keys[2], before: 0, after: 1_000 },
+            ]
+        );
+        assert_eq!(changes[0].delta(), -6_000);
+        assert_eq!(changes[1].delta(), 1_000);
+    }
+}
@@ -0,0 +1,48 @@
+use std::str::FromStr;
+
+/// Number of decimal places of SOL.
+const SOL_DECIMALS: u8 = 9;
+
+/// An amount of lamports, parsed either from a plain integer of lamports
+/// (`5000`) or from a decimal amount of SOL with a `sol` suffix (`0.5sol`).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Amount(pub u64);
+
+impl FromStr for Amount {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+
+        let Some(sol) = s.strip_suffix("sol").or_else(|| s.strip_suffix("SOL")) else {
+            return s
+                .parse()
+                .map(Amount)
+                .map_err(|_| format!("invalid amount `{s}`, expected lamports or an amount of SOL like `0.5sol`"));
+        };
+
+        bootcamp_common::parse_ui_amount(sol, SOL_DECIMALS).map(Amount)
+    }
+}
+
+/// A `<PUBKEY>:<AMOUNT>` pair given to `batch`.
+#[derive(Clone, Debug)]
+pub struct Recipient {
+    pub pubkey: solana_sdk::pubkey::Pubkey,
+    pub amount: Amount,
+}
+
+impl FromStr for Recipient {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (pubkey, amount) = s
+            .split_once(':')
+            .ok_or_else(|| format!("invalid recipient `{s}`, expected <PUBKEY>:<AMOUNT>"))?;
+
+        Ok(Recipient {
+            pubkey: pubkey.parse().map_err(|_| format!("invalid pubkey `{pubkey}`"))?,
+            amount: amount.parse()?,
+        })
+    }
+}
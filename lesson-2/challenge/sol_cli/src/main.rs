@@ -0,0 +1,148 @@
+use amount::{Amount, Recipient};
+use anyhow::Result;
+use bootcamp_common::{ClusterArgs, Context, KeypairArgs, SimulateArgs, Submitted};
+use clap::{Parser, Subcommand};
+use solana_sdk::{
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+};
+use std::path::PathBuf;
+
+mod amount;
+mod payout;
+mod system;
+
+/// Creates system accounts and transfers SOL on a Solana cluster.
+///
+/// Amounts are lamports (`5000`) or SOL with a `sol` suffix (`0.5sol`).
+#[derive(Parser)]
+#[command(version)]
+struct Cli {
+    // fee payer, which also funds every transfer
+    #[command(flatten)]
+    keypair: KeypairArgs,
+
+    #[command(flatten)]
+    cluster: ClusterArgs,
+
+    #[command(flatten)]
+    simulate: SimulateArgs,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Create a new rent exempt account
+    CreateAccount {
+        /// Data size of the new account in bytes
+        #[arg(long, default_value_t = 0)]
+        space: u64,
+    },
+
+    /// Transfer lamports to an existing account
+    Transfer {
+        /// Receiver of the transfer
+        recipient: Pubkey,
+
+        amount: Amount,
+    },
+
+    /// Create a new account and transfer lamports to it in one transaction
+    CreateAndTransfer {
+        /// Amount transferred on top of the rent of the new account
+        amount: Amount,
+
+        /// Data size of the new account in bytes
+        #[arg(long, default_value_t = 0)]
+        space: u64,
+    },
+
+    /// Create and fund a new account, and pay other recipients in the same transaction
+    Batch {
+        /// Amount transferred on top of the rent of the new account
+        amount: Amount,
+
+        /// Another account to pay, as <PUBKEY>:<AMOUNT>; may be repeated
+        #[arg(long = "to", value_name = "PUBKEY:AMOUNT", required = true)]
+        recipients: Vec<Recipient>,
+
+        /// Data size of the new account in bytes
+        #[arg(long, default_value_t = 0)]
+        space: u64,
+    },
+
+    /// Pay every row of a `pubkey,amount` CSV file, packing several transfers per transaction
+    ///
+    /// Rerun the same command to resume a failed or interrupted payout: rows recorded as
+    /// paid in the results file are skipped, and pending ones are looked up on the cluster.
+    Payout {
+        /// CSV file with a `pubkey,amount` header
+        input: PathBuf,
+
+        /// Where to record the status and signature of every row [default: <INPUT>.results.csv]
+        #[arg(long)]
+        results: Option<PathBuf>,
+    },
+}
+
+fn print_new_account(submitted: &Submitted, new_account: &Keypair) {
+    match submitted {
+        Submitted::Sent(_) => println!("New account created: {}", new_account.pubkey()),
+        Submitted::Simulated => println!("New account (not created): {}", new_account.pubkey()),
+    }
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+
+    let cluster = cli.cluster.resolve()?;
+    let client = cluster.rpc_client();
+    let payer = cli.keypair.read_keypair()?;
+
+    let context = Context {
+        client: &client,
+        cluster: &cluster,
+        payer: &payer,
+        simulate: cli.simulate.simulate,
+    };
+
+    match cli.command {
+        Command::CreateAccount { space } => {
+            let new_account = Keypair::new();
+
+            let submitted = system::create_account(&context, &new_account, space)?;
+
+            print_new_account(&submitted, &new_account);
+        }
+        Command::Transfer { recipient, amount } => {
+            system::transfer_lamport(&context, &recipient, amount.0)?;
+        }
+        Command::CreateAndTransfer { amount, space } => {
+            let new_account = Keypair::new();
+
+            let submitted = system::create_acc_transfer(&context, &new_account, space, amount.0)?;
+
+            print_new_account(&submitted, &new_account);
+        }
+        Command::Batch { amount, recipients, space } => {
+            let new_account = Keypair::new();
+            let recipients: Vec<(Pubkey, u64)> = recipients
+                .iter()
+                .map(|recipient| (recipient.pubkey, recipient.amount.0))
+                .collect();
+
+            let submitted = system::create_and_transfer(&context, &new_account, &recipients, space, amount.0)?;
+
+            print_new_account(&submitted, &new_account);
+        }
+        Command::Payout { input, results } => {
+            let results = results.unwrap_or_else(|| input.with_extension("results.csv"));
+
+            payout::payout(&context, &input, &results)?;
+        }
+    }
+
+    Ok(())
+}
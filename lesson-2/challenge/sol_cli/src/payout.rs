@@ -0,0 +1,445 @@
+use crate::amount::Amount;
+use anyhow::{bail, Context as _, Result};
+use bootcamp_common::manifest::{self, Rows, Status};
+use bootcamp_common::Context;
+use serde::{Deserialize, Serialize};
+use solana_sdk::{
+    instruction::Instruction,
+    message::Message,
+    packet::PACKET_DATA_SIZE,
+    pubkey::Pubkey,
+    signature::Signer,
+    system_instruction,
+};
+use std::{path::Path, str::FromStr};
+
+/// A row of the input CSV.
+#[derive(Deserialize)]
+struct InputRow {
+    pubkey: String,
+    amount: String,
+}
+
+/// A validated payment, `row` being its 1-based position in the input CSV.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Payment {
+    row: usize,
+    recipient: Pubkey,
+    lamports: u64,
+}
+
+/// A row of the results CSV, keyed by the input row it pays.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct ResultRow {
+    row: usize,
+    pubkey: String,
+    lamports: u64,
+    status: Status,
+    signature: Option<String>,
+    blockhash: Option<String>,
+    error: Option<String>,
+}
+
+impl manifest::Row for ResultRow {
+    type Key = usize;
+
+    fn key(&self) -> usize {
+        self.row
+    }
+
+    fn status(&self) -> Status {
+        self.status
+    }
+
+    fn signature(&self) -> Option<&str> {
+        self.signature.as_deref()
+    }
+
+    fn blockhash(&self) -> Option<&str> {
+        self.blockhash.as_deref()
+    }
+
+    fn settle(&mut self, status: Status, error: Option<String>) {
+        self.status = status;
+        self.error = error;
+    }
+}
+
+/// Pays every `pubkey,amount` row of the `input` CSV, packing as many transfers
+/// per transaction as fit, and records the outcome of every row in `results`.
+///
+/// Rows are written as pending with their signature before the transaction is
+/// sent. Rerunning with the same files skips the rows already paid and looks up
+/// pending ones on the cluster first, so a row is never paid twice.
+///
+/// When the context simulates, the transactions that are left to send are
+/// simulated instead and the results file is left untouched.
+pub fn payout(context: &Context, input: &Path, results: &Path) -> Result<()> {
+    let client = context.client;
+    let payer = context.payer;
+
+    let payments = read_payments(input)?;
+    let mut result_rows = read_results(results, &payments)?;
+
+    manifest::resolve_pending(client, &mut result_rows)?;
+
+    if !context.simulate {
+        manifest::write(results, &result_rows)?;
+    }
+
+    let unpaid = unpaid(&payments, &result_rows);
+
+    println!(
+        "{} of {} rows already paid, {} to pay",
+        payments.len() - unpaid.len(),
+        payments.len(),
+        unpaid.len()
+    );
+
+    let batches = pack(&payer.pubkey(), &unpaid);
+
+    // every batch is a transaction signed by the payer alone, so they all pay
+    // the same fee
+    let fee = match batches.first() {
+        Some(batch) => {
+            let blockhash = client.get_latest_blockhash()?;
+            let message = Message::new_with_blockhash(&transfers(&payer.pubkey(), batch), Some(&payer.pubkey()), &blockhash);
+
+            client.get_fee_for_message(&message)?
+        }
+        None => 0,
+    };
+
+    let Some(total) = required_lamports(&unpaid, batches.len(), fee) else {
+        bail!("The payments and fees add up to more than u64::MAX lamports");
+    };
+    let balance = client.get_balance(&payer.pubkey())?;
+
+    if balance < total {
+        bail!(
+            "Payer {} holds {balance} lamports, not enough to pay {total} lamports including {} transaction fees",
+            payer.pubkey(),
+            batches.len()
+        );
+    }
+
+    for batch in batches {
+        let transaction = context.sign_transaction(&[], &transfers(&payer.pubkey(), &batch))?;
+        let signature = transaction.signatures[0];
+        let blockhash = transaction.message.recent_blockhash;
+
+        if context.simulate {
+            println!("Simulating transfers of rows {}", rows(&batch));
+            context.submit_transaction(&transaction)?;
+            continue;
+        }
+
+        for payment in &batch {
+            result_rows.insert(
+                payment.row,
+                ResultRow {
+                    row: payment.row,
+                    pubkey: payment.recipient.to_string(),
+                    lamports: payment.lamports,
+                    status: Status::Pending,
+                    signature: Some(signature.to_string()),
+                    blockhash: Some(blockhash.to_string()),
+                    error: None,
+                },
+            );
+        }
+        manifest::write(results, &result_rows)?;
+
+        let (status, error) = manifest::send(context, &transaction);
+
+        for payment in &batch {
+            let result = result_rows.get_mut(&payment.row).expect("row was just inserted");
+            result.status = status;
+            result.error = error.clone();
+        }
+        manifest::write(results, &result_rows)?;
+    }
+
+    if context.simulate {
+        return Ok(());
+    }
+
+    let count = |status| result_rows.values().filter(|result| result.status == status).count();
+    println!(
+        "{} paid, {} failed, {} pending; results written to {}",
+        count(Status::Success),
+        count(Status::Failed),
+        count(Status::Pending),
+        results.display()
+    );
+
+    if count(Status::Failed) + count(Status::Pending) > 0 {
+        bail!("Some rows were not paid, rerun the same command to retry them");
+    }
+
+    Ok(())
+}
+
+/// The payments left to send: those without a result and those that failed,
+/// which paid nothing. Pending rows are settled by `manifest::resolve_pending` beforehand.
+fn unpaid(payments: &[Payment], result_rows: &Rows<ResultRow>) -> Vec<Payment> {
+    payments
+        .iter()
+        .filter(|payment| manifest::is_unsent(result_rows, &payment.row))
+        .copied()
+        .collect()
+}
+
+/// Lamports the payer spends on `payments` sent in `batches` transactions of
+/// `fee` lamports each, `None` if that overflows.
+fn required_lamports(payments: &[Payment], batches: usize, fee: u64) -> Option<u64> {
+    let fees = fee.checked_mul(u64::try_from(batches).ok()?)?;
+
+    payments
+        .iter()
+        .try_fold(fees, |total, payment| total.checked_add(payment.lamports))
+}
+
+/// Comma separated input rows of `batch`, for progress messages.
+fn rows(batch: &[Payment]) -> String {
+    batch
+        .iter()
+        .map(|payment| payment.row.to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Reads and validates the whole input, reporting every invalid row at once.
+fn read_payments(input: &Path) -> Result<Vec<Payment>> {
+    let mut reader = csv::ReaderBuilder::new()
+        .trim(csv::Trim::All)
+        .from_path(input)
+        .with_context(|| format!("Unable to open payout file {}", input.display()))?;
+
+    let mut payments = Vec::new();
+    let mut errors = Vec::new();
+
+    for (index, record) in reader.deserialize::<InputRow>().enumerate() {
+        let row = index + 1;
+
+        let parsed = record.map_err(|err| err.to_string()).and_then(|record| {
+            let recipient =
+                Pubkey::from_str(&record.pubkey).map_err(|_| format!("invalid pubkey `{}`", record.pubkey))?;
+            let Amount(lamports) = record.amount.parse()?;
+
+            if lamports == 0 {
+                return Err("amount must be greater than zero".to_string());
+            }
+
+            Ok(Payment { row, recipient, lamports })
+        });
+
+        match parsed {
+            Ok(payment) => payments.push(payment),
+            Err(err) => errors.push(format!("row {row}: {err}")),
+        }
+    }
+
+    if !errors.is_empty() {
+        bail!("Invalid payout file {}:\n{}", input.display(), errors.join("\n"));
+    }
+
+    Ok(payments)
+}
+
+/// Reads the results of a previous run, if any, checking they were written for
+/// the same input.
+fn read_results(results: &Path, payments: &[Payment]) -> Result<Rows<ResultRow>> {
+    manifest::read(results, |result: &ResultRow| {
+        let matches = payments.get(result.row.wrapping_sub(1)).is_some_and(|payment| {
+            payment.recipient.to_string() == result.pubkey && payment.lamports == result.lamports
+        });
+
+        if !matches {
+            bail!(
+                "Row {} of {} does not match the payout file, was the payout file edited?",
+                result.row,
+                results.display()
+            );
+        }
+
+        Ok(())
+    })
+}
+
+/// Greedily groups payments into transactions that stay within the packet size.
+fn pack(payer: &Pubkey, payments: &[Payment]) -> Vec<Vec<Payment>> {
+    let mut batches: Vec<Vec<Payment>> = Vec::new();
+    let mut batch: Vec<Payment> = Vec::new();
+
+    for payment in payments {
+        batch.push(*payment);
+
+        if batch.len() > 1 && transaction_size(payer, &batch) > PACKET_DATA_SIZE {
+            batch.pop();
+            batches.push(std::mem::replace(&mut batch, vec![*payment]));
+        }
+    }
+
+    if !batch.is_empty() {
+        batches.push(batch);
+    }
+
+    batches
+}
+
+fn transfers(payer: &Pubkey, batch: &[Payment]) -> Vec<Instruction> {
+    batch
+        .iter()
+        .map(|payment| system_instruction::transfer(payer, &payment.recipient, payment.lamports))
+        .collect()
+}
+
+/// Size of the signed transaction paying `batch`: the signature count, the
+/// single payer signature and the message.
+fn transaction_size(payer: &Pubkey, batch: &[Payment]) -> usize {
+    1 + 64 + Message::new(&transfers(payer, batch), Some(payer)).serialize().len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::{hash::Hash, signature::Signature};
+    use std::{collections::BTreeMap, fs};
+    use tempfile::NamedTempFile;
+
+    fn csv_file(contents: &str) -> NamedTempFile {
+        let file = NamedTempFile::new().unwrap();
+        fs::write(file.path(), contents).unwrap();
+
+        file
+    }
+
+    fn payments(count: usize) -> Vec<Payment> {
+        (1..=count)
+            .map(|row| Payment {
+                row,
+                recipient: Pubkey::new_unique(),
+                lamports: 1_000 * row as u64,
+            })
+            .collect()
+    }
+
+    fn result(payment: &Payment, status: Status) -> ResultRow {
+        ResultRow {
+            row: payment.row,
+            pubkey: payment.recipient.to_string(),
+            lamports: payment.lamports,
+            status,
+            signature: Some(Signature::new_unique().to_string()),
+            blockhash: Some(Hash::new_unique().to_string()),
+            error: None,
+        }
+    }
+
+    #[test]
+    fn reads_payments() {
+        let recipient = Pubkey::new_unique();
+        let file = csv_file(&format!("pubkey,amount\n{recipient}, 1.5sol\n{recipient},1\n"));
+
+        let payments = read_payments(file.path()).unwrap();
+
+        assert_eq!(
+            payments,
+            [
+                Payment { row: 1, recipient, lamports: 1_500_000_000 },
+                Payment { row: 2, recipient, lamports: 1 },
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_every_invalid_payment() {
+        let recipient = Pubkey::new_unique();
+        let file = csv_file(&format!("pubkey,amount\nnot-a-pubkey,1\n{recipient},0\n{recipient},1\n{recipient},abc\n"));
+
+        let err = read_payments(file.path()).unwrap_err().to_string();
+
+        assert!(err.contains("row 1: invalid pubkey `not-a-pubkey`"), "{err}");
+        assert!(err.contains("row 2: amount must be greater than zero"), "{err}");
+        assert!(!err.contains("row 3"), "{err}");
+        assert!(err.contains("row 4"), "{err}");
+    }
+
+    #[test]
+    fn reads_results_of_the_same_payout() {
+        let payments = payments(2);
+        let file = NamedTempFile::new().unwrap();
+        let results = file.path().with_extension("csv");
+
+        assert!(read_results(&results, &payments).unwrap().is_empty());
+
+        let rows = BTreeMap::from([
+            (1, result(&payments[0], Status::Success)),
+            (2, result(&payments[1], Status::Pending)),
+        ]);
+        manifest::write(&results, &rows).unwrap();
+
+        let read = read_results(&results, &payments).unwrap();
+        assert_eq!(read.len(), 2);
+        assert_eq!(read[&1].status, Status::Success);
+        assert_eq!(read[&2].signature, rows[&2].signature);
+
+        // the amount of row 2 was edited since
+        let mut edited = payments.clone();
+        edited[1].lamports += 1;
+        let err = read_results(&results, &edited).unwrap_err().to_string();
+        assert!(err.contains("Row 2"), "{err}");
+
+        // row 2 was removed from the payout file
+        let err = read_results(&results, &payments[..1]).unwrap_err().to_string();
+        assert!(err.contains("Row 2"), "{err}");
+
+        fs::remove_file(results).unwrap();
+    }
+
+    #[test]
+    fn retries_failed_payments_only() {
+        let payments = payments(4);
+        let rows = BTreeMap::from([
+            (1, result(&payments[0], Status::Success)),
+            (2, result(&payments[1], Status::Pending)),
+            (3, result(&payments[2], Status::Failed)),
+        ]);
+
+        assert_eq!(unpaid(&payments, &rows), [payments[2], payments[3]]);
+    }
+
+    #[test]
+    fn packs_payments_within_the_packet_size() {
+        let payer = Pubkey::new_unique();
+        let payments = payments(50);
+
+        let batches = pack(&payer, &payments);
+
+        assert!(batches.len() > 1);
+        assert!(batches.iter().all(|batch| transaction_size(&payer, batch) <= PACKET_DATA_SIZE));
+        // every batch but the last is full
+        for (batch, next) in batches.iter().zip(&batches[1..]) {
+            let mut grown = batch.clone();
+            grown.push(next[0]);
+            assert!(transaction_size(&payer, &grown) > PACKET_DATA_SIZE);
+        }
+        assert_eq!(batches.concat(), payments);
+
+        assert!(pack(&payer, &[]).is_empty());
+    }
+
+    #[test]
+    fn required_lamports_include_fees() {
+        let payments = payments(3);
+
+        assert_eq!(required_lamports(&payments, 2, 5_000), Some(6_000 + 10_000));
+        assert_eq!(required_lamports(&[], 0, 5_000), Some(0));
+
+        let mut huge = payments.clone();
+        huge[0].lamports = u64::MAX;
+        assert_eq!(required_lamports(&huge, 1, 0), None);
+        assert_eq!(required_lamports(&payments, 2, u64::MAX), None);
+    }
+}
@@ -0,0 +1,72 @@
+use anyhow::Result;
+use bootcamp_common::{Context, Submitted};
+use solana_sdk::{
+    instruction::Instruction,
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+    system_instruction, system_program,
+};
+
+fn create_account_instruction(context: &Context, new_account: &Keypair, space: u64) -> Result<Instruction> {
+    let rent = context.client.get_minimum_balance_for_rent_exemption(space.try_into()?)?;
+
+    Ok(system_instruction::create_account(
+        &context.payer.pubkey(),
+        &new_account.pubkey(),
+        rent,
+        space,
+        &system_program::id(),
+    ))
+}
+
+/// Creates a rent exempt system account of `space` bytes.
+pub fn create_account(context: &Context, new_account: &Keypair, space: u64) -> Result<Submitted> {
+    let create_instr = create_account_instruction(context, new_account, space)?;
+
+    context.send_transaction(&[new_account], &[create_instr])
+}
+
+pub fn transfer_lamport(context: &Context, receiver_account: &Pubkey, transfer_amount: u64) -> Result<Submitted> {
+    let transfer_instr = system_instruction::transfer(&context.payer.pubkey(), receiver_account, transfer_amount);
+
+    context.send_transaction(&[], &[transfer_instr])
+}
+
+/// Creates a new account and funds it with `transfer_amount` on top of its rent.
+pub fn create_acc_transfer(
+    context: &Context,
+    new_receiver_account: &Keypair,
+    space: u64,
+    transfer_amount: u64,
+) -> Result<Submitted> {
+    let create_acc_instr = create_account_instruction(context, new_receiver_account, space)?;
+    let transfer_instr =
+        system_instruction::transfer(&context.payer.pubkey(), &new_receiver_account.pubkey(), transfer_amount);
+
+    context.send_transaction(&[new_receiver_account], &[create_acc_instr, transfer_instr])
+}
+
+/// Creates and funds a new account like [`create_acc_transfer`], and pays every
+/// recipient in the same transaction.
+pub fn create_and_transfer(
+    context: &Context,
+    new_receiver_account: &Keypair,
+    recipients: &[(Pubkey, u64)],
+    space: u64,
+    transfer_new_acc: u64,
+) -> Result<Submitted> {
+    let payer = context.payer.pubkey();
+
+    let mut instructions = vec![
+        create_account_instruction(context, new_receiver_account, space)?,
+        system_instruction::transfer(&payer, &new_receiver_account.pubkey(), transfer_new_acc),
+    ];
+
+    instructions.extend(
+        recipients
+            .iter()
+            .map(|(receiver, amount)| system_instruction::transfer(&payer, receiver, *amount)),
+    );
+
+    context.send_transaction(&[new_receiver_account], &instructions)
+}
@@ -0,0 +1,90 @@
+use mpl_token_metadata::accounts::{MasterEdition, Metadata};
+use mpl_token_metadata::instructions as metadata_instruction;
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::{signature::Keypair, signer::Signer};
+
+use anyhow::{bail, Result};
+use bootcamp_common::Context;
+
+use crate::nft::Membership;
+
+/// Fetches the metadata of the collection NFT `collection`, failing unless it
+/// is a sized collection.
+pub fn fetch_collection(client: &RpcClient, collection: &Pubkey) -> Result<Metadata> {
+    let metadata = bootcamp_common::fetch_metadata(client, collection)?;
+
+    if metadata.collection_details.is_none() {
+        bail!("Mint {} is not a sized collection, create one with `create-collection`", collection);
+    }
+
+    Ok(metadata)
+}
+
+/// Membership of a new item of `collection`, verified right away when `payer`
+/// is the update authority of the collection.
+pub fn item_of(client: &RpcClient, collection: &Pubkey, payer: &Pubkey) -> Result<Membership> {
+    let verify = fetch_collection(client, collection)?.update_authority == *payer;
+
+    if !verify {
+        println!(
+            "the payer is not the update authority of collection {}, run `verify-collection` with it",
+            collection
+        );
+    }
+
+    Ok(Membership::Item { collection: *collection, verify })
+}
+
+/// Instruction verifying that the NFT `mint` belongs to the sized collection
+/// `collection`, signed by the update authority of the collection.
+pub fn verify_instruction(payer: &Pubkey, authority: &Pubkey, mint: &Pubkey, collection: &Pubkey) -> Instruction {
+    metadata_instruction::VerifySizedCollectionItem {
+        metadata: Metadata::find_pda(mint).0,
+        collection_authority: *authority,
+        payer: *payer,
+        collection_mint: *collection,
+        collection: Metadata::find_pda(collection).0,
+        collection_master_edition_account: MasterEdition::find_pda(collection).0,
+        collection_authority_record: None,
+    }
+    .instruction()
+}
+
+/// Verifies the membership of an NFT minted with `--collection` but left
+/// unverified, signed by `authority`, the update authority of the collection.
+pub fn verify_collection(context: &Context, authority: &Keypair, mint: &Pubkey, collection: &Pubkey) -> Result<()> {
+    let item = bootcamp_common::fetch_metadata(context.client, mint)?;
+
+    match &item.collection {
+        Some(item_collection) if item_collection.key != *collection => bail!(
+            "NFT {} belongs to collection {}, not {}",
+            mint,
+            item_collection.key,
+            collection
+        ),
+        Some(item_collection) if item_collection.verified => {
+            println!("NFT {} is already verified in collection {}", mint, collection);
+            return Ok(());
+        }
+        Some(_) => {}
+        None => bail!("NFT {} does not reference any collection", mint),
+    }
+
+    let collection_metadata = fetch_collection(context.client, collection)?;
+
+    if collection_metadata.update_authority != authority.pubkey() {
+        bail!(
+            "Only the update authority {} of collection {} can verify its items",
+            collection_metadata.update_authority,
+            collection
+        );
+    }
+
+    let instruction = verify_instruction(&context.payer.pubkey(), &authority.pubkey(), mint, collection);
+
+    context.send_transaction(&[authority], &[instruction])?;
+
+    Ok(())
+}
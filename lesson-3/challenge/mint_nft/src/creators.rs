@@ -0,0 +1,185 @@
+use mpl_token_metadata::accounts::Metadata;
+use mpl_token_metadata::instructions as metadata_instruction;
+use mpl_token_metadata::types::Creator;
+use mpl_token_metadata::MAX_CREATOR_LIMIT;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::{signature::Keypair, signer::Signer};
+use std::str::FromStr;
+
+use anyhow::{bail, Result};
+use bootcamp_common::Context;
+
+/// A `<PUBKEY>:<SHARE>` pair given to `--creator`, the share being a
+/// percentage of the royalties.
+#[derive(Clone, Copy, Debug)]
+pub struct CreatorShare {
+    pub address: Pubkey,
+    pub share: u8,
+}
+
+impl FromStr for CreatorShare {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (address, share) = s
+            .split_once(':')
+            .ok_or_else(|| format!("invalid creator `{s}`, expected <PUBKEY>:<SHARE>"))?;
+
+        Ok(CreatorShare {
+            address: address.parse().map_err(|_| format!("invalid pubkey `{address}`"))?,
+            share: share
+                .parse()
+                .map_err(|_| format!("invalid share `{share}`, expected a percentage from 0 to 100"))?,
+        })
+    }
+}
+
+/// Builds the creators of a new NFT from the `--creator` flags, the payer
+/// alone getting every royalty when none are given.
+///
+/// Shares must add up to 100 and every creator must be listed once. Only the
+/// payer can be verified right away, as it signs the creation of the metadata;
+/// the other creators verify themselves afterwards with `sign-creator`.
+pub fn creators(shares: &[CreatorShare], payer: &Pubkey) -> Result<Vec<Creator>> {
+    if shares.is_empty() {
+        return Ok(vec![Creator {
+            address: *payer,
+            verified: true,
+            share: 100,
+        }]);
+    }
+
+    if shares.len() > MAX_CREATOR_LIMIT {
+        bail!("An NFT has at most {} creators, {} given", MAX_CREATOR_LIMIT, shares.len());
+    }
+
+    for (index, creator) in shares.iter().enumerate() {
+        if shares[..index].iter().any(|other| other.address == creator.address) {
+            bail!("Creator {} is listed more than once", creator.address);
+        }
+    }
+
+    let total: u32 = shares.iter().map(|creator| u32::from(creator.share)).sum();
+    if total != 100 {
+        bail!("Creator shares add up to {}%, not 100%", total);
+    }
+
+    Ok(shares
+        .iter()
+        .map(|creator| Creator {
+            address: creator.address,
+            verified: creator.address == *payer,
+            share: creator.share,
+        })
+        .collect())
+}
+
+/// Verifies `creator` on the metadata of the NFT `mint`, proving it agreed to
+/// be listed as one of its creators.
+pub fn sign_creator(context: &Context, creator: &Keypair, mint: &Pubkey) -> Result<()> {
+    let metadata = bootcamp_common::fetch_metadata(context.client, mint)?;
+
+    match metadata
+        .creators
+        .unwrap_or_default()
+        .iter()
+        .find(|listed| listed.address == creator.pubkey())
+    {
+        Some(listed) if listed.verified => {
+            println!("Creator {} is already verified on NFT {}", creator.pubkey(), mint);
+            return Ok(());
+        }
+        Some(_) => {}
+        None => bail!("{} is not a creator of NFT {}", creator.pubkey(), mint),
+    }
+
+    let instruction = metadata_instruction::SignMetadata {
+        metadata: Metadata::find_pda(mint).0,
+        creator: creator.pubkey(),
+    }
+    .instruction();
+
+    context.send_transaction(&[creator], &[instruction])?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn share(address: Pubkey, share: u8) -> CreatorShare {
+        CreatorShare { address, share }
+    }
+
+    #[test]
+    fn payer_alone_by_default() {
+        let payer = Pubkey::new_unique();
+
+        assert_eq!(
+            creators(&[], &payer).unwrap(),
+            [Creator { address: payer, verified: true, share: 100 }]
+        );
+    }
+
+    #[test]
+    fn verifies_the_payer_only() {
+        let (payer, co_creator) = (Pubkey::new_unique(), Pubkey::new_unique());
+
+        assert_eq!(
+            creators(&[share(payer, 70), share(co_creator, 30)], &payer).unwrap(),
+            [
+                Creator { address: payer, verified: true, share: 70 },
+                Creator { address: co_creator, verified: false, share: 30 },
+            ]
+        );
+
+        let listed = creators(&[share(co_creator, 100)], &payer).unwrap();
+        assert!(!listed[0].verified);
+    }
+
+    #[test]
+    fn shares_add_up_to_100() {
+        let (first, second) = (Pubkey::new_unique(), Pubkey::new_unique());
+
+        for (a, b, total) in [(50, 49, 99), (50, 51, 101)] {
+            let err = creators(&[share(first, a), share(second, b)], &first).unwrap_err();
+            assert_eq!(err.to_string(), format!("Creator shares add up to {total}%, not 100%"));
+        }
+
+        // the sum is not truncated to a `u8`
+        let err = creators(&[share(first, 200), share(second, 156)], &first).unwrap_err();
+        assert!(err.to_string().contains("356%"), "{err}");
+    }
+
+    #[test]
+    fn rejects_duplicate_creators() {
+        let (payer, creator) = (Pubkey::new_unique(), Pubkey::new_unique());
+
+        let err = creators(&[share(creator, 50), share(payer, 0), share(creator, 50)], &payer).unwrap_err();
+        assert_eq!(err.to_string(), format!("Creator {creator} is listed more than once"));
+    }
+
+    #[test]
+    fn rejects_more_than_five_creators() {
+        let payer = Pubkey::new_unique();
+        let five: Vec<CreatorShare> = (0..5).map(|_| share(Pubkey::new_unique(), 20)).collect();
+        assert_eq!(creators(&five, &payer).unwrap().len(), 5);
+
+        let six: Vec<CreatorShare> = (0..6).map(|index| share(Pubkey::new_unique(), if index == 0 { 50 } else { 10 })).collect();
+        let err = creators(&six, &payer).unwrap_err();
+        assert_eq!(err.to_string(), "An NFT has at most 5 creators, 6 given");
+    }
+
+    #[test]
+    fn parses_creator_shares() {
+        let address = Pubkey::new_unique();
+
+        let parsed: CreatorShare = format!("{address}:30").parse().unwrap();
+        assert_eq!((parsed.address, parsed.share), (address, 30));
+
+        assert!("no-separator".parse::<CreatorShare>().is_err());
+        assert!(format!("{address}:256").parse::<CreatorShare>().is_err());
+        assert!("not-a-pubkey:30".parse::<CreatorShare>().is_err());
+    }
+}
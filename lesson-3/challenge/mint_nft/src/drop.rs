@@ -0,0 +1,358 @@
+use solana_sdk::{signature::Keypair, signer::Signer};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context as _, Result};
+use bootcamp_common::manifest::{self, Rows, Status};
+use bootcamp_common::Context;
+use serde::{Deserialize, Serialize};
+
+use crate::nft::{self, Membership, NftArgs, NftOptions};
+
+/// A numbered metadata file of the drop.
+#[derive(Debug)]
+struct Item {
+    number: u32,
+    name: String,
+    symbol: String,
+    uri: String,
+}
+
+/// A row of the manifest, keyed by the number of the metadata file it mints.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct ManifestRow {
+    number: u32,
+    uri: String,
+    mint: String,
+    status: Status,
+    signature: Option<String>,
+    blockhash: Option<String>,
+    error: Option<String>,
+}
+
+impl manifest::Row for ManifestRow {
+    type Key = u32;
+
+    fn key(&self) -> u32 {
+        self.number
+    }
+
+    fn status(&self) -> Status {
+        self.status
+    }
+
+    fn signature(&self) -> Option<&str> {
+        self.signature.as_deref()
+    }
+
+    fn blockhash(&self) -> Option<&str> {
+        self.blockhash.as_deref()
+    }
+
+    fn settle(&mut self, status: Status, error: Option<String>) {
+        self.status = status;
+        self.error = error;
+    }
+}
+
+/// Mints an NFT to the payer for every numbered metadata file of `dir`, in
+/// order, and records the mint and signature of each in `manifest`.
+///
+/// Rows are written as pending with their signature before the transaction is
+/// sent. Rerunning with the same directory and manifest skips the NFTs already
+/// minted and looks up pending ones on the cluster first, so a file is never
+/// minted twice.
+///
+/// With `simulate` set, the transactions that are left to send are simulated
+/// instead and the manifest is left untouched.
+pub fn mint_drop(
+    context: &Context,
+    dir: &Path,
+    base_uri: &str,
+    options: &NftOptions,
+    membership: Membership,
+    manifest: &Path,
+) -> Result<()> {
+    let simulate = context.simulate;
+
+    let items = read_items(dir, base_uri)?;
+    let mut rows = read_manifest(manifest, &items)?;
+
+    manifest::resolve_pending(context.client, &mut rows)?;
+
+    if !simulate {
+        manifest::write(manifest, &rows)?;
+    }
+
+    let unminted = unminted(&items, &rows);
+
+    println!(
+        "{} of {} NFTs already minted, {} to mint",
+        items.len() - unminted.len(),
+        items.len(),
+        unminted.len()
+    );
+
+    for item in unminted {
+        let mint = Keypair::new();
+        let instructions = nft::instructions(
+            context,
+            &mint,
+            NftArgs {
+                name: item.name.clone(),
+                symbol: item.symbol.clone(),
+                uri: item.uri.clone(),
+                options: options.clone(),
+            },
+            membership,
+        )?;
+
+        let transaction = context.sign_transaction(&[&mint], &instructions)?;
+        let signature = transaction.signatures[0];
+        let blockhash = transaction.message.recent_blockhash;
+
+        if simulate {
+            println!("Simulating the mint of #{}", item.number);
+            context.submit_transaction(&transaction)?;
+            continue;
+        }
+
+        rows.insert(
+            item.number,
+            ManifestRow {
+                number: item.number,
+                uri: item.uri.clone(),
+                mint: mint.pubkey().to_string(),
+                status: Status::Pending,
+                signature: Some(signature.to_string()),
+                blockhash: Some(blockhash.to_string()),
+                error: None,
+            },
+        );
+        manifest::write(manifest, &rows)?;
+
+        let (status, error) = manifest::send(context, &transaction);
+        if status == Status::Success {
+            println!("#{} minted: {}", item.number, mint.pubkey());
+        }
+
+        let row = rows.get_mut(&item.number).expect("row was just inserted");
+        row.status = status;
+        row.error = error;
+        manifest::write(manifest, &rows)?;
+    }
+
+    if simulate {
+        return Ok(());
+    }
+
+    let count = |status| rows.values().filter(|row| row.status == status).count();
+    println!(
+        "{} minted, {} failed, {} pending; manifest written to {}",
+        count(Status::Success),
+        count(Status::Failed),
+        count(Status::Pending),
+        manifest.display()
+    );
+
+    if count(Status::Failed) + count(Status::Pending) > 0 {
+        bail!("Some NFTs were not minted, rerun the same command to retry them");
+    }
+
+    Ok(())
+}
+
+/// The items left to mint: those without a row and those that failed, which
+/// minted nothing. Pending rows are settled by `manifest::resolve_pending` beforehand.
+fn unminted<'a>(items: &'a [Item], rows: &Rows<ManifestRow>) -> Vec<&'a Item> {
+    items
+        .iter()
+        .filter(|item| manifest::is_unsent(rows, &item.number))
+        .collect()
+}
+
+/// Reads and validates every `<N>.json` file of `dir`, sorted by number and
+/// reporting every invalid file at once. Other files are ignored.
+fn read_items(dir: &Path, base_uri: &str) -> Result<Vec<Item>> {
+    let mut files: Vec<(u32, PathBuf)> = Vec::new();
+
+    for entry in fs::read_dir(dir).with_context(|| format!("Unable to read drop directory {}", dir.display()))? {
+        let path = entry?.path();
+
+        if path.extension().is_some_and(|extension| extension == "json") {
+            if let Some(number) = path.file_stem().and_then(|stem| stem.to_str()?.parse().ok()) {
+                files.push((number, path));
+            }
+        }
+    }
+
+    if files.is_empty() {
+        bail!("No numbered metadata files (0.json, 1.json, ...) in {}", dir.display());
+    }
+
+    files.sort();
+
+    let mut items = Vec::new();
+    let mut errors = Vec::new();
+
+    for (number, path) in files {
+        let uri = format!("{}/{}.json", base_uri.trim_end_matches('/'), number);
+
+        let item = bootcamp_common::metadata::read_metadata_file(&path).and_then(|metadata| {
+            bootcamp_common::check_on_chain_fields(&metadata.name, &metadata.symbol, &uri)?;
+
+            Ok(Item {
+                number,
+                name: metadata.name,
+                symbol: metadata.symbol,
+                uri,
+            })
+        });
+
+        match item {
+            Ok(item) => items.push(item),
+            Err(err) => errors.push(err.to_string()),
+        }
+    }
+
+    if !errors.is_empty() {
+        bail!("Invalid drop directory {}:\n{}", dir.display(), errors.join("\n"));
+    }
+
+    Ok(items)
+}
+
+/// Reads the manifest of a previous run, if any, checking it was written for
+/// the same drop.
+fn read_manifest(manifest: &Path, items: &[Item]) -> Result<Rows<ManifestRow>> {
+    manifest::read(manifest, |row: &ManifestRow| {
+        if !items.iter().any(|item| item.number == row.number && item.uri == row.uri) {
+            bail!(
+                "NFT #{} of {} does not match the drop, were the files or --base-uri changed?",
+                row.number,
+                manifest.display()
+            );
+        }
+
+        Ok(())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::{hash::Hash, pubkey::Pubkey, signature::Signature};
+    use std::collections::BTreeMap;
+
+    const BASE_URI: &str = "https://example.com/drop/";
+
+    fn metadata(name: &str) -> String {
+        format!(r#"{{"name": "{name}", "symbol": "DROP", "image": "https://example.com/drop/0.png"}}"#)
+    }
+
+    fn drop_dir(files: &[(&str, String)]) -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        for (name, contents) in files {
+            fs::write(dir.path().join(name), contents).unwrap();
+        }
+
+        dir
+    }
+
+    fn row(number: u32, status: Status) -> ManifestRow {
+        ManifestRow {
+            number,
+            uri: format!("{BASE_URI}{number}.json"),
+            mint: Pubkey::new_unique().to_string(),
+            status,
+            signature: Some(Signature::new_unique().to_string()),
+            blockhash: Some(Hash::new_unique().to_string()),
+            error: None,
+        }
+    }
+
+    #[test]
+    fn reads_numbered_files_only() {
+        let dir = drop_dir(&[
+            ("10.json", metadata("Drop #10")),
+            ("2.json", metadata("Drop #2")),
+            ("cover.json", metadata("Cover")),
+            ("2.png", String::new()),
+            ("notes.txt", "not metadata".to_string()),
+        ]);
+
+        let items = read_items(dir.path(), BASE_URI).unwrap();
+
+        assert_eq!(items.iter().map(|item| item.number).collect::<Vec<_>>(), [2, 10]);
+        assert_eq!(items[0].name, "Drop #2");
+        assert_eq!(items[0].symbol, "DROP");
+        assert_eq!(items[0].uri, "https://example.com/drop/2.json");
+        assert_eq!(items[1].uri, "https://example.com/drop/10.json");
+    }
+
+    #[test]
+    fn reports_every_invalid_file() {
+        let dir = drop_dir(&[
+            ("0.json", metadata("Drop #0")),
+            ("1.json", "{".to_string()),
+            ("2.json", metadata(&"a".repeat(33))),
+        ]);
+
+        let err = read_items(dir.path(), BASE_URI).unwrap_err().to_string();
+
+        assert!(err.contains("1.json"), "{err}");
+        assert!(err.contains("2.json"), "{err}");
+        assert!(err.contains("name is 33 bytes long"), "{err}");
+        assert!(!err.contains("0.json"), "{err}");
+
+        let empty = drop_dir(&[("notes.txt", String::new())]);
+        assert!(read_items(empty.path(), BASE_URI).is_err());
+    }
+
+    #[test]
+    fn reads_manifest_of_the_same_drop() {
+        let dir = drop_dir(&[("0.json", metadata("Drop #0")), ("1.json", metadata("Drop #1"))]);
+        let items = read_items(dir.path(), BASE_URI).unwrap();
+        let manifest = dir.path().join("manifest.csv");
+
+        assert!(read_manifest(&manifest, &items).unwrap().is_empty());
+
+        let rows = BTreeMap::from([(0, row(0, Status::Success)), (1, row(1, Status::Pending))]);
+        manifest::write(&manifest, &rows).unwrap();
+
+        let read = read_manifest(&manifest, &items).unwrap();
+        assert_eq!(read.len(), 2);
+        assert_eq!(read[&0].mint, rows[&0].mint);
+        assert_eq!(read[&1].status, Status::Pending);
+        assert_eq!(read[&1].signature, rows[&1].signature);
+
+        // the files were uploaded elsewhere since the manifest was written
+        let moved = read_items(dir.path(), "https://example.org/drop").unwrap();
+        let err = read_manifest(&manifest, &moved).unwrap_err().to_string();
+        assert!(err.contains("NFT #0"), "{err}");
+
+        // a file was removed from the drop
+        let err = read_manifest(&manifest, &items[..1]).unwrap_err().to_string();
+        assert!(err.contains("NFT #1"), "{err}");
+    }
+
+    #[test]
+    fn retries_failed_items_only() {
+        let dir = drop_dir(&[
+            ("0.json", metadata("Drop #0")),
+            ("1.json", metadata("Drop #1")),
+            ("2.json", metadata("Drop #2")),
+            ("3.json", metadata("Drop #3")),
+        ]);
+        let items = read_items(dir.path(), BASE_URI).unwrap();
+        let rows = BTreeMap::from([
+            (0, row(0, Status::Success)),
+            (1, row(1, Status::Pending)),
+            (2, row(2, Status::Failed)),
+        ]);
+
+        let unminted = unminted(&items, &rows);
+
+        assert_eq!(unminted.iter().map(|item| item.number).collect::<Vec<_>>(), [2, 3]);
+    }
+}
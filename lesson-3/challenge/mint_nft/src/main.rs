@@ -1,154 +1,163 @@
-use mpl_token_metadata::instructions as metadata_instruction;
-use mpl_token_metadata::instructions::CreateMetadataAccountV3InstructionArgs;
-use mpl_token_metadata::types::DataV2;
-use mpl_token_metadata::ID as metadata_program_id;
-use solana_client::rpc_client::RpcClient;
 use solana_sdk::pubkey::Pubkey;
-use solana_sdk::system_program;
-use solana_sdk::transaction::Transaction;
-use solana_sdk::{program_pack::Pack, signature::Keypair, signer::Signer, system_instruction};
-use spl_associated_token_account::get_associated_token_address;
-use spl_token::instruction::{self as token_instruction, AuthorityType};
-use spl_associated_token_account::instruction;
-use spl_token::state::Mint;
-use spl_token::ID as token_program_id;
+use solana_sdk::{signature::Keypair, signer::Signer};
 
 use anyhow::Result;
-use std::fs::File;
-
-const NFT_ROYALTY: u16 = 1000;
-
-mod util {
-    pub fn get_signature_explorer_url(signature: &str) -> String {
-        format!("https://explorer.solana.com/tx/{}?cluster=devnet", signature)
-    }
+use bootcamp_common::{ClusterArgs, Context, KeypairArgs, MetadataAccountCommand, MetadataCommand, SimulateArgs};
+use clap::{Parser, Subcommand};
+use nft::{Membership, NftArgs, NftOptions};
+use std::path::PathBuf;
+
+mod collection;
+mod creators;
+mod drop;
+mod nft;
+
+/// Mints NFTs and groups them into verified collections.
+#[derive(Parser)]
+struct Cli {
+    #[command(flatten)]
+    cluster: ClusterArgs,
+
+    #[command(flatten)]
+    keypair: KeypairArgs,
+
+    #[command(flatten)]
+    simulate: SimulateArgs,
+
+    #[command(subcommand)]
+    command: Command,
 }
 
-fn read_keypair_from_file(filepath: &str) -> Keypair {
-    let file = File::open(filepath).expect("Unable to open keypair file");
-    let keypair: Vec<u8> = serde_json::from_reader(file).expect("Unable to parse keypair file");
-    return Keypair::from_bytes(&keypair).expect("Unable to create keypair from bytes");
+#[derive(Subcommand)]
+enum Command {
+    /// Mint an NFT, optionally as an item of a collection
+    Mint {
+        #[command(flatten)]
+        nft: NftArgs,
+
+        /// Mint of the collection NFT the new NFT belongs to. The membership is verified
+        /// in the same transaction when the payer is the update authority of the collection
+        #[arg(long)]
+        collection: Option<Pubkey>,
+    },
+
+    /// Create the NFT of a sized collection, which items can then be minted into
+    CreateCollection {
+        #[command(flatten)]
+        nft: NftArgs,
+    },
+
+    /// Mint one NFT per numbered metadata file (`0.json`, `1.json`, ...) of a directory.
+    /// Rerun the same command to resume an interrupted drop
+    Drop {
+        /// Directory of the metadata files
+        dir: PathBuf,
+
+        /// URI the directory is uploaded to; each NFT gets <BASE_URI>/<N>.json
+        #[arg(long)]
+        base_uri: String,
+
+        /// Mint of the collection NFT every NFT of the drop belongs to
+        #[arg(long)]
+        collection: Option<Pubkey>,
+
+        /// Where to record the mint and signature of every NFT [default: <DIR>/manifest.csv]
+        #[arg(long)]
+        manifest: Option<PathBuf>,
+
+        #[command(flatten)]
+        options: NftOptions,
+    },
+
+    /// Verify that an NFT minted with `--collection` belongs to the collection
+    VerifyCollection {
+        /// Mint of the NFT to verify
+        mint: Pubkey,
+
+        /// Mint of the collection NFT
+        collection: Pubkey,
+
+        /// Keypair file of the update authority of the collection [default: the payer]
+        #[arg(long, value_name = "KEYPAIR")]
+        authority: Option<String>,
+    },
+
+    /// Verify a co-creator listed on an NFT, who signs to agree to it
+    SignCreator {
+        /// Mint of the NFT
+        mint: Pubkey,
+
+        /// Keypair file of the creator [default: the payer]
+        #[arg(long, value_name = "KEYPAIR")]
+        creator: Option<String>,
+    },
+
+    #[command(flatten)]
+    MetadataAccount(MetadataAccountCommand),
+
+    #[command(flatten)]
+    Metadata(MetadataCommand),
 }
 
-fn mint_my_first_token (
-    client: &RpcClient,
-    payer: &Keypair,
-    nft_mint_account_key: &Keypair,
-) -> Result<()> {
-    let (metadata_account_address, _) = Pubkey::find_program_address(
-        &[
-            b"metadata",
-            &metadata_program_id.to_bytes(),
-            &nft_mint_account_key.pubkey().to_bytes(),
-        ],
-        &metadata_program_id
-    );
-
-    let associated_token_account_address = get_associated_token_address(&payer.pubkey(), &nft_mint_account_key.pubkey());
-
-    let rent = client.get_minimum_balance_for_rent_exemption(Mint::LEN)?;
-    let create_nft_mint_account = system_instruction::create_account(
-        &payer.pubkey(),
-        &nft_mint_account_key.pubkey(),
-        rent,
-        Mint::LEN as u64,
-        &token_program_id,
-    );
-
-    let init_nft_mint_account = token_instruction::initialize_mint(
-        &token_program_id,
-        &nft_mint_account_key.pubkey(),
-        &payer.pubkey(),
-        Some(&nft_mint_account_key.pubkey()),
-        0,
-    );
-
-    let create_metadata_account = metadata_instruction::CreateMetadataAccountV3 {
-        metadata: metadata_account_address,
-        mint: nft_mint_account_key.pubkey(),
-        mint_authority: payer.pubkey(),
-        payer: payer.pubkey(),
-        update_authority: (payer.pubkey(), true),
-        system_program: system_program::id(),
-        rent: None
-    }
-    .instruction(CreateMetadataAccountV3InstructionArgs {
-        data: DataV2 {
-            name: "Test Token".to_string(),
-            symbol: "TT".to_string(),
-            uri: "https://raw.githubusercontent.com/hoang-nguyen-huy/fptu-solana-bootcamp/main/assets/tt-token.json".to_string(),
-            seller_fee_basis_points: NFT_ROYALTY,
-            creators: None,
-            collection: None, 
-            uses: None,
-        },
-        is_mutable: true, 
-        collection_details: None, 
-    });
-
-    let create_nft = instruction::create_associated_token_account(
-        &payer.pubkey(),
-        &payer.pubkey(),
-        &nft_mint_account_key.pubkey(),
-        &token_program_id,
-    );
-
-    let init_nft = token_instruction::mint_to(
-        &token_program_id,
-        &nft_mint_account_key.pubkey(),
-        &associated_token_account_address,
-        &payer.pubkey(),
-        &[&payer.pubkey()],
-        1,
-    );
-
-    let remove_mint_authority = token_instruction::set_authority(
-        &token_program_id,
-        &nft_mint_account_key.pubkey(),
-        None,
-        AuthorityType::MintTokens,
-        &payer.pubkey(),
-        &[&payer.pubkey(), &nft_mint_account_key.pubkey()],
-    );
-
-    let mut instructions = Vec::with_capacity(5);
-    instructions.push(create_nft_mint_account);
-    instructions.push(init_nft_mint_account?);
-    instructions.push(create_metadata_account);
-
-    if client.get_account(&associated_token_account_address).is_err() {
-        instructions.push(create_nft);
-    }
-    instructions.push(init_nft?);
-    instructions.push(remove_mint_authority?);
-
-    let create_blockhash = client.get_latest_blockhash()?;
-    let create_transaction = Transaction::new_signed_with_payer(
-        &instructions,
-        Some(&payer.pubkey()),
-        &[&payer, &nft_mint_account_key],
-        create_blockhash,
-    );
-
-    let create_sig = client.send_and_confirm_transaction(&create_transaction)?;
-
-    let explorer_url = util::get_signature_explorer_url(&create_sig.to_string());
-
-    println!("explorer url: {}", explorer_url);
-
-//  https://explorer.solana.com/tx/5zjxnYRbE8bV3zZ59Xfdf6jwnKRfpgk68pgyseNw3jbPz7UwXvC4En3zznjRaVREPs4SYo8XUtU4ZUVuLLiQENfR?cluster=devnet
-
-    Ok(())
+/// Reads the keypair file of an authority given on the command line, if any.
+fn read_authority(path: Option<&str>) -> Result<Option<Keypair>> {
+    Ok(path.map(|path| bootcamp_common::read_keypair_file(path, None)).transpose()?)
 }
 
 fn main() -> Result<()> {
-    let rpc_url = "https://api.devnet.solana.com";
-    let client = RpcClient::new(rpc_url.to_string());
-
-    let payer = read_keypair_from_file("../payer-keypair.json");
-    let nft_mint_account_key = Keypair::new();
-
-    mint_my_first_token(&client, &payer, &nft_mint_account_key)?;
+    let cli = Cli::parse();
+
+    // metadata files are handled offline, without a cluster or a keypair
+    let command = match cli.command {
+        Command::Metadata(command) => return command.run(),
+        command => command,
+    };
+
+    let cluster = cli.cluster.resolve()?;
+    let client = cluster.rpc_client();
+
+    let payer = cli.keypair.read_keypair()?;
+    let context = Context {
+        client: &client,
+        cluster: &cluster,
+        payer: &payer,
+        simulate: cli.simulate.simulate,
+    };
+
+    match command {
+        Command::Mint { nft, collection } => {
+            let membership = match collection {
+                Some(collection) => collection::item_of(&client, &collection, &payer.pubkey())?,
+                None => Membership::Standalone,
+            };
+
+            nft::mint_nft(&context, &Keypair::new(), nft, membership)?;
+        }
+        Command::CreateCollection { nft } => {
+            nft::mint_nft(&context, &Keypair::new(), nft, Membership::Collection)?;
+        }
+        Command::Drop { dir, base_uri, collection, manifest, options } => {
+            let membership = match collection {
+                Some(collection) => collection::item_of(&client, &collection, &payer.pubkey())?,
+                None => Membership::Standalone,
+            };
+            let manifest = manifest.unwrap_or_else(|| dir.join("manifest.csv"));
+
+            drop::mint_drop(&context, &dir, &base_uri, &options, membership, &manifest)?;
+        }
+        Command::VerifyCollection { mint, collection, authority } => {
+            let authority = read_authority(authority.as_deref())?;
+
+            collection::verify_collection(&context, authority.as_ref().unwrap_or(&payer), &mint, &collection)?;
+        }
+        Command::SignCreator { mint, creator } => {
+            let creator = read_authority(creator.as_deref())?;
+
+            creators::sign_creator(&context, creator.as_ref().unwrap_or(&payer), &mint)?;
+        }
+        Command::MetadataAccount(command) => command.run(&context)?,
+        Command::Metadata(_) => unreachable!("handled before connecting"),
+    }
 
     Ok(())
 }
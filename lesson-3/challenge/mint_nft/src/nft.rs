@@ -0,0 +1,223 @@
+use mpl_token_metadata::accounts::{MasterEdition, Metadata};
+use mpl_token_metadata::instructions as metadata_instruction;
+use mpl_token_metadata::instructions::{CreateMasterEditionV3InstructionArgs, CreateMetadataAccountV3InstructionArgs};
+use mpl_token_metadata::types::{Collection, CollectionDetails, DataV2};
+use mpl_token_metadata::ID as metadata_program_id;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::system_program;
+use solana_sdk::{signature::Keypair, signer::Signer, system_instruction};
+use spl_token_2022::extension::ExtensionType;
+use spl_token_2022::instruction as token_instruction;
+use spl_token_2022::state::Mint;
+
+use anyhow::{bail, Result};
+use bootcamp_common::{Context, TokenProgram};
+use clap::Args;
+
+use crate::collection;
+use crate::creators::{self, CreatorShare};
+
+const NFT_ROYALTY: u16 = 1000;
+
+const DEFAULT_URI: &str = "https://raw.githubusercontent.com/hoang-nguyen-huy/fptu-solana-bootcamp/main/assets/tt-token.json";
+
+#[derive(Args)]
+pub struct NftArgs {
+    #[arg(long, default_value = "Test Token")]
+    pub name: String,
+
+    #[arg(long, default_value = "TT")]
+    pub symbol: String,
+
+    /// URI of the off-chain metadata JSON
+    #[arg(long, default_value = DEFAULT_URI)]
+    pub uri: String,
+
+    #[command(flatten)]
+    pub options: NftOptions,
+}
+
+/// Options of an NFT besides its metadata, shared by every NFT of a drop.
+#[derive(Args, Clone)]
+pub struct NftOptions {
+    /// Token program owning the mint
+    #[arg(long, value_enum, default_value_t)]
+    token_program: TokenProgram,
+
+    /// Make the NFT soulbound: it can never leave the payer's wallet. Requires `token-2022`
+    #[arg(long)]
+    non_transferable: bool,
+
+    /// Creator sharing the royalties, as <PUBKEY>:<SHARE> with shares in percent adding up to 100;
+    /// may be repeated [default: the payer with a 100% share]
+    #[arg(long = "creator", value_name = "PUBKEY:SHARE")]
+    creators: Vec<CreatorShare>,
+}
+
+/// Place of a new NFT with regard to collections.
+#[derive(Clone, Copy)]
+pub enum Membership {
+    /// A standalone NFT.
+    Standalone,
+    /// The NFT of a sized collection, which its items point to.
+    Collection,
+    /// An item of the collection with this mint, verified in the same
+    /// transaction when `verify` is set.
+    Item { collection: Pubkey, verify: bool },
+}
+
+/// Mints a single NFT to the payer, in one transaction.
+pub fn mint_nft(
+    context: &Context,
+    nft_mint_account_key: &Keypair,
+    nft: NftArgs,
+    membership: Membership,
+) -> Result<()> {
+    let instructions = instructions(context, nft_mint_account_key, nft, membership)?;
+
+    println!("mint: {}", nft_mint_account_key.pubkey());
+    println!("metadata: {}", Metadata::find_pda(&nft_mint_account_key.pubkey()).0);
+
+    context.send_transaction(&[nft_mint_account_key], &instructions)?;
+
+    Ok(())
+}
+
+/// Instructions minting a single NFT to the payer, signed by the payer and
+/// the new mint.
+///
+/// Every NFT gets a master edition with a max supply of 0, which takes over
+/// the mint and freeze authorities so no second token or print can ever be
+/// minted, as wallets and marketplaces expect of an NFT and the metadata
+/// program requires for verifying collection items.
+pub fn instructions(
+    context: &Context,
+    nft_mint_account_key: &Keypair,
+    nft: NftArgs,
+    membership: Membership,
+) -> Result<Vec<Instruction>> {
+    let Context { client, payer, .. } = *context;
+
+    bootcamp_common::check_on_chain_fields(&nft.name, &nft.symbol, &nft.uri)?;
+
+    let token_program_id = nft.options.token_program.id();
+    let creators = creators::creators(&nft.options.creators, &payer.pubkey())?;
+
+    let mut extensions = Vec::new();
+    if nft.options.non_transferable {
+        if nft.options.token_program != TokenProgram::Token2022 {
+            bail!("--non-transferable requires --token-program token-2022");
+        }
+        extensions.push(ExtensionType::NonTransferable);
+    }
+
+    let (metadata_account_address, _) = Pubkey::find_program_address(
+        &[
+            b"metadata",
+            &metadata_program_id.to_bytes(),
+            &nft_mint_account_key.pubkey().to_bytes(),
+        ],
+        &metadata_program_id
+    );
+
+    let associated_token_account = bootcamp_common::ensure_associated_token_account(
+        client,
+        &payer.pubkey(),
+        &payer.pubkey(),
+        &nft_mint_account_key.pubkey(),
+        &token_program_id,
+    )?;
+
+    let space = ExtensionType::try_calculate_account_len::<Mint>(&extensions)?;
+    let rent = client.get_minimum_balance_for_rent_exemption(space)?;
+    let create_nft_mint_account = system_instruction::create_account(
+        &payer.pubkey(),
+        &nft_mint_account_key.pubkey(),
+        rent,
+        space as u64,
+        &token_program_id,
+    );
+
+    let init_nft_mint_account = token_instruction::initialize_mint(
+        &token_program_id,
+        &nft_mint_account_key.pubkey(),
+        &payer.pubkey(),
+        // held by the payer until the master edition takes it over
+        Some(&payer.pubkey()),
+        0,
+    );
+
+    let create_metadata_account = metadata_instruction::CreateMetadataAccountV3 {
+        metadata: metadata_account_address,
+        mint: nft_mint_account_key.pubkey(),
+        mint_authority: payer.pubkey(),
+        payer: payer.pubkey(),
+        update_authority: (payer.pubkey(), true),
+        system_program: system_program::id(),
+        rent: None
+    }
+    .instruction(CreateMetadataAccountV3InstructionArgs {
+        data: DataV2 {
+            name: nft.name,
+            symbol: nft.symbol,
+            uri: nft.uri,
+            seller_fee_basis_points: NFT_ROYALTY,
+            creators: Some(creators),
+            collection: match membership {
+                Membership::Item { collection, .. } => Some(Collection { verified: false, key: collection }),
+                _ => None,
+            },
+            uses: None,
+        },
+        is_mutable: true,
+        collection_details: match membership {
+            Membership::Collection => Some(CollectionDetails::V1 { size: 0 }),
+            _ => None,
+        },
+    });
+
+    let init_nft = token_instruction::mint_to(
+        &token_program_id,
+        &nft_mint_account_key.pubkey(),
+        &associated_token_account.address,
+        &payer.pubkey(),
+        &[&payer.pubkey()],
+        1,
+    );
+
+    let mut instructions = Vec::with_capacity(8);
+    instructions.push(create_nft_mint_account);
+    if nft.options.non_transferable {
+        instructions.push(token_instruction::initialize_non_transferable_mint(&token_program_id, &nft_mint_account_key.pubkey())?);
+    }
+    instructions.push(init_nft_mint_account?);
+    instructions.push(create_metadata_account);
+
+    instructions.extend(associated_token_account.create);
+    instructions.push(init_nft?);
+
+    instructions.push(metadata_instruction::CreateMasterEditionV3 {
+        edition: MasterEdition::find_pda(&nft_mint_account_key.pubkey()).0,
+        mint: nft_mint_account_key.pubkey(),
+        update_authority: payer.pubkey(),
+        mint_authority: payer.pubkey(),
+        payer: payer.pubkey(),
+        metadata: metadata_account_address,
+        token_program: token_program_id,
+        system_program: system_program::id(),
+        rent: None,
+    }
+    .instruction(CreateMasterEditionV3InstructionArgs { max_supply: Some(0) }));
+
+    if let Membership::Item { collection, verify: true } = membership {
+        instructions.push(collection::verify_instruction(
+            &payer.pubkey(),
+            &payer.pubkey(),
+            &nft_mint_account_key.pubkey(),
+            &collection,
+        ));
+    }
+
+    Ok(instructions)
+}
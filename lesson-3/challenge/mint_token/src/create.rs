@@ -0,0 +1,285 @@
+use mpl_token_metadata::instructions as metadata_instruction;
+use mpl_token_metadata::instructions::CreateMetadataAccountV3InstructionArgs;
+use mpl_token_metadata::types::DataV2;
+use mpl_token_metadata::ID as metadata_program_id; // mpl_token_metadat: dùng làm việc vs metada của token
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::system_program;
+use solana_sdk::{signature::Keypair, signer::Signer, system_instruction};
+use spl_token_2022::extension::{interest_bearing_mint, metadata_pointer, transfer_fee, ExtensionType};
+use spl_token_2022::instruction as token_instruction;
+use spl_token_2022::state::Mint;
+use spl_token_metadata_interface::state::TokenMetadata;
+
+use anyhow::{anyhow, bail, Result};
+use bootcamp_common::{Context, TokenProgram};
+use clap::Args;
+
+
+const DEFAULT_URI: &str = "https://raw.githubusercontent.com/hoang-nguyen-huy/fptu-solana-bootcamp/main/assets/tt-token.json";
+
+#[derive(Args)]
+pub struct CreateArgs {
+    /// Number of decimal places of the token
+    #[arg(long, default_value_t = 6)]
+    decimals: u8,
+
+    /// Initial supply in whole tokens, e.g. `100` or `2.5`
+    #[arg(long, default_value = "100")]
+    supply: String,
+
+    #[arg(long, default_value = "Test Token")]
+    name: String,
+
+    #[arg(long, default_value = "TT")]
+    symbol: String,
+
+    /// URI of the off-chain metadata JSON
+    #[arg(long, default_value = DEFAULT_URI)]
+    uri: String,
+
+    /// Account allowed to freeze token accounts of the mint; the mint can't be frozen if omitted
+    #[arg(long)]
+    freeze_authority: Option<Pubkey>,
+
+    /// Generate a new freeze authority and save its keypair to this file, which must not exist yet
+    #[arg(long, value_name = "FILE", conflicts_with = "freeze_authority")]
+    new_freeze_authority: Option<String>,
+
+    /// Owner of the token account receiving the initial supply [default: the payer]
+    #[arg(long)]
+    recipient: Option<Pubkey>,
+
+    /// Token program owning the mint; the extensions below require `token-2022`
+    #[arg(long, value_enum, default_value_t)]
+    token_program: TokenProgram,
+
+    /// Fee withheld on every transfer, in basis points of the amount
+    #[arg(long, requires = "max_transfer_fee")]
+    transfer_fee_bps: Option<u16>,
+
+    /// Cap of the transfer fee in whole tokens
+    #[arg(long, requires = "transfer_fee_bps")]
+    max_transfer_fee: Option<String>,
+
+    /// Store the name, symbol and URI in the mint itself, pointed to by its metadata pointer, instead of a Metaplex metadata account
+    #[arg(long)]
+    embedded_metadata: bool,
+
+    /// Forbid transferring the tokens once minted, e.g. for badges
+    #[arg(long)]
+    non_transferable: bool,
+
+    /// Interest rate in basis points per year, applied to the UI amounts of balances; may be negative
+    #[arg(long, allow_hyphen_values = true)]
+    interest_rate_bps: Option<i16>,
+}
+
+impl CreateArgs {
+    /// Initial supply in base units of the mint.
+    pub fn supply(&self) -> Result<u64> {
+        bootcamp_common::parse_ui_amount(&self.supply, self.decimals).map_err(|err| anyhow!("Invalid supply: {err}"))
+    }
+
+    /// Extensions of the mint requested on the command line.
+    fn extensions(&self) -> Vec<ExtensionType> {
+        let mut extensions = Vec::new();
+
+        if self.transfer_fee_bps.is_some() {
+            extensions.push(ExtensionType::TransferFeeConfig);
+        }
+        if self.embedded_metadata {
+            extensions.push(ExtensionType::MetadataPointer);
+        }
+        if self.non_transferable {
+            extensions.push(ExtensionType::NonTransferable);
+        }
+        if self.interest_rate_bps.is_some() {
+            extensions.push(ExtensionType::InterestBearingConfig);
+        }
+
+        extensions
+    }
+}
+
+pub fn create_token (
+    context: &Context,
+    mint_account_key: &Keypair,
+    token: CreateArgs,
+) -> Result<()> {
+    let Context { client, payer, simulate, .. } = *context;
+
+    let supply = token.supply()?;
+    let recipient = token.recipient.unwrap_or_else(|| payer.pubkey());
+    let token_program_id = token.token_program.id();
+    let extensions = token.extensions();
+
+    if !extensions.is_empty() && token.token_program != TokenProgram::Token2022 {
+        bail!("Mint extensions require --token-program token-2022");
+    }
+
+    // embedded metadata has no length limits, the Token Metadata program does
+    if !token.embedded_metadata {
+        bootcamp_common::check_on_chain_fields(&token.name, &token.symbol, &token.uri)?;
+    }
+
+    let mut freeze_authority = token.freeze_authority;
+
+    if let Some(path) = &token.new_freeze_authority {
+        let keypair = Keypair::new();
+
+        // saved before anything is sent so the authority of the new mint can't be lost
+        if !simulate {
+            let path = bootcamp_common::write_keypair_file(path, &keypair)?;
+            println!("freeze authority {} saved to {}", keypair.pubkey(), path.display());
+        }
+
+        freeze_authority = Some(keypair.pubkey());
+    }
+
+    let (metadata_account_address, _) = Pubkey::find_program_address(
+        &[
+            b"metadata",
+            &metadata_program_id.to_bytes(),
+            &mint_account_key.pubkey().to_bytes(),
+        ],
+        &metadata_program_id
+    ); // để tìm địa chỉ chương trình
+
+    let associated_token_account = bootcamp_common::ensure_associated_token_account(
+        client,
+        &payer.pubkey(),
+        &recipient,
+        &mint_account_key.pubkey(),
+        &token_program_id,
+    )?; // lấy địa chỉ tài khoản token liên kết
+    let associated_token_account_address = associated_token_account.address;
+
+    // embedded metadata is written after the mint is initialized, which grows the
+    // account: it is created with room for the extensions only, but funded for both
+    let embedded_metadata = token.embedded_metadata.then(|| TokenMetadata {
+        name: token.name.clone(),
+        symbol: token.symbol.clone(),
+        uri: token.uri.clone(),
+        mint: mint_account_key.pubkey(),
+        ..TokenMetadata::default()
+    });
+
+    let space = ExtensionType::try_calculate_account_len::<Mint>(&extensions)?;
+    let metadata_space = match &embedded_metadata {
+        Some(metadata) => metadata.tlv_size_of()?,
+        None => 0,
+    };
+
+    let rent = client.get_minimum_balance_for_rent_exemption(space + metadata_space)?;
+    let create_mint_account = system_instruction::create_account(
+        &payer.pubkey(),
+        &mint_account_key.pubkey(),
+        rent,
+        space as u64,
+        &token_program_id,
+    );
+
+    let mut instructions = vec![create_mint_account];
+
+    // extensions are initialized before the mint itself
+    if let (Some(transfer_fee_bps), Some(max_transfer_fee)) = (token.transfer_fee_bps, &token.max_transfer_fee) {
+        let max_transfer_fee = bootcamp_common::parse_ui_amount(max_transfer_fee, token.decimals)
+            .map_err(|err| anyhow!("Invalid maximum transfer fee: {err}"))?;
+
+        instructions.push(transfer_fee::instruction::initialize_transfer_fee_config(
+            &token_program_id,
+            &mint_account_key.pubkey(),
+            Some(&payer.pubkey()),
+            Some(&payer.pubkey()),
+            transfer_fee_bps,
+            max_transfer_fee,
+        )?);
+    }
+    if token.embedded_metadata {
+        instructions.push(metadata_pointer::instruction::initialize(
+            &token_program_id,
+            &mint_account_key.pubkey(),
+            Some(payer.pubkey()),
+            Some(mint_account_key.pubkey()),
+        )?);
+    }
+    if token.non_transferable {
+        instructions.push(token_instruction::initialize_non_transferable_mint(
+            &token_program_id,
+            &mint_account_key.pubkey(),
+        )?);
+    }
+    if let Some(interest_rate_bps) = token.interest_rate_bps {
+        instructions.push(interest_bearing_mint::instruction::initialize(
+            &token_program_id,
+            &mint_account_key.pubkey(),
+            Some(payer.pubkey()),
+            interest_rate_bps,
+        )?);
+    }
+
+    instructions.push(token_instruction::initialize_mint(
+        &token_program_id,
+        &mint_account_key.pubkey(),
+        &payer.pubkey(),
+        freeze_authority.as_ref(),
+        token.decimals,
+    )?);
+
+    match embedded_metadata {
+        Some(metadata) => instructions.push(spl_token_metadata_interface::instruction::initialize(
+            &token_program_id,
+            &mint_account_key.pubkey(),
+            &payer.pubkey(),
+            &mint_account_key.pubkey(),
+            &payer.pubkey(),
+            metadata.name,
+            metadata.symbol,
+            metadata.uri,
+        )),
+        None => instructions.push(metadata_instruction::CreateMetadataAccountV3 {
+            metadata: metadata_account_address,
+            mint: mint_account_key.pubkey(),
+            mint_authority: payer.pubkey(),
+            payer: payer.pubkey(),
+            update_authority: (payer.pubkey(), true),
+            system_program: system_program::id(),
+            rent: None
+        }
+        .instruction(CreateMetadataAccountV3InstructionArgs {
+            data: DataV2 {
+                name: token.name,
+                symbol: token.symbol,
+                uri: token.uri,
+                seller_fee_basis_points: 0,
+                creators: None,
+                collection: None,
+                uses: None,
+            },
+            is_mutable: true,
+            collection_details: None,
+        })),
+    }
+
+    instructions.extend(associated_token_account.create);
+    instructions.push(token_instruction::mint_to(
+        &token_program_id,
+        &mint_account_key.pubkey(),
+        &associated_token_account_address,
+        &payer.pubkey(),
+        &[&payer.pubkey()],
+        supply,
+    )?);
+
+    println!("mint: {}", mint_account_key.pubkey());
+    if token.embedded_metadata {
+        println!("metadata: embedded in the mint");
+    } else {
+        println!("metadata: {}", metadata_account_address);
+    }
+    println!("token account of {}: {}", recipient, associated_token_account_address);
+
+    context.send_transaction(&[mint_account_key], &instructions)?;
+
+    Ok(())
+}
@@ -1,145 +1,237 @@
-use mpl_token_metadata::instructions as metadata_instruction;
-use mpl_token_metadata::instructions::CreateMetadataAccountV3InstructionArgs;
-use mpl_token_metadata::types::DataV2;
-use mpl_token_metadata::ID as metadata_program_id; // mpl_token_metadat: dùng làm việc vs metada của token
-use solana_client::rpc_client::RpcClient; // solana_client, solana_sdk: cung cấp các chức năng để tương tác với mạng Solana
 use solana_sdk::pubkey::Pubkey;
-use solana_sdk::system_program;
-use solana_sdk::transaction::Transaction;
-use solana_sdk::{program_pack::Pack, signature::Keypair, signer::Signer, system_instruction};
-use spl_associated_token_account::get_associated_token_address; 
-use spl_associated_token_account::instruction; // spl_associated_token_account, spl_token: thư viện SPL để làm việc với token trên Solana
-use spl_token::instruction as token_instruction;
-use spl_token::state::Mint;
-use spl_token::ID as token_program_id;
-
-use anyhow::Result;
-use std::fs::File;
-
-const MINT_DECIMALS: u8 = 6;
-const MINT_AMOUNT: u64 = 100;
-
-mod util {
-    pub fn get_signature_explorer_url(signature: &str) -> String {
-        format!("https://explorer.solana.com/tx/{}?cluster=devnet", signature)
-    }
+use solana_sdk::{signature::Keypair, signer::Signer};
+
+use anyhow::{bail, Result};
+use bootcamp_common::{ClusterArgs, Context, KeypairArgs, MetadataAccountCommand, MetadataCommand, SimulateArgs};
+use clap::{Parser, Subcommand};
+use create::CreateArgs;
+use supply::MintAuthority;
+
+mod create;
+mod supply;
+
+/// Creates fungible tokens and manages their supply.
+///
+/// Amounts are whole tokens (`2.5`), converted with the decimals of the mint.
+#[derive(Parser)]
+struct Cli {
+    #[command(flatten)]
+    cluster: ClusterArgs,
+
+    #[command(flatten)]
+    keypair: KeypairArgs,
+
+    #[command(flatten)]
+    simulate: SimulateArgs,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Create a token with its metadata and mint its initial supply, in a single transaction
+    Create(CreateArgs),
+
+    /// Mint more tokens
+    Mint {
+        mint: Pubkey,
+
+        amount: String,
+
+        /// Owner of the token account receiving the tokens, created if needed [default: the payer]
+        #[arg(long)]
+        recipient: Option<Pubkey>,
+
+        /// Keypair file of the mint authority [default: the payer]
+        #[arg(long, value_name = "KEYPAIR")]
+        authority: Option<String>,
+    },
+
+    /// Burn tokens from a token account
+    Burn {
+        mint: Pubkey,
+
+        amount: String,
+
+        /// Keypair file of the owner of the token account [default: the payer]
+        #[arg(long, value_name = "KEYPAIR")]
+        owner: Option<String>,
+    },
+
+    /// Freeze the token account of an owner
+    Freeze {
+        mint: Pubkey,
+
+        owner: Pubkey,
+
+        /// Keypair file of the freeze authority [default: the payer]
+        #[arg(long, value_name = "KEYPAIR")]
+        authority: Option<String>,
+    },
+
+    /// Thaw a frozen token account of an owner
+    Thaw {
+        mint: Pubkey,
+
+        owner: Pubkey,
+
+        /// Keypair file of the freeze authority [default: the payer]
+        #[arg(long, value_name = "KEYPAIR")]
+        authority: Option<String>,
+    },
+
+    /// Hand an authority of the mint over to another account, or revoke it for good
+    SetAuthority {
+        mint: Pubkey,
+
+        #[arg(value_enum)]
+        kind: MintAuthority,
+
+        /// New holder of the authority
+        #[arg(long, required_unless_present = "revoke")]
+        new_authority: Option<Pubkey>,
+
+        /// Revoke the authority permanently, nobody will ever hold it again
+        #[arg(long, conflicts_with = "new_authority")]
+        revoke: bool,
+
+        /// Keypair file of the current authority [default: the payer]
+        #[arg(long, value_name = "KEYPAIR")]
+        authority: Option<String>,
+    },
+
+    #[command(flatten)]
+    MetadataAccount(MetadataAccountCommand),
+
+    #[command(flatten)]
+    Metadata(MetadataCommand),
 }
 
-fn read_keypair_from_file(filepath: &str) -> Keypair {
-    let file = File::open(filepath).expect("Unable to open keypair file");
-    let keypair: Vec<u8> = serde_json::from_reader(file).expect("Unable to parse keypair file");
-    return Keypair::from_bytes(&keypair).expect("Unable to create keypair from bytes");
+/// Reads the keypair file of an authority given on the command line, if any.
+fn read_authority(path: Option<&str>) -> Result<Option<Keypair>> {
+    Ok(path.map(|path| bootcamp_common::read_keypair_file(path, None)).transpose()?)
 }
 
-fn mint_my_first_token (
-    client: &RpcClient,
-    payer: &Keypair,
-    mint_account_key: &Keypair,
-) -> Result<()> {
-    let (metadata_account_address, _) = Pubkey::find_program_address(
-        &[
-            b"metadata",
-            &metadata_program_id.to_bytes(),
-            &mint_account_key.pubkey().to_bytes(),
-        ],
-        &metadata_program_id
-    ); // để tìm địa chỉ chương trình
-
-    let associated_token_account_address = get_associated_token_address(&payer.pubkey(), &mint_account_key.pubkey()); // lấy địa chỉ tài khoản token liên kết
-
-    let rent = client.get_minimum_balance_for_rent_exemption(Mint::LEN)?;
-    let create_mint_account = system_instruction::create_account(
-        &payer.pubkey(),
-        &mint_account_key.pubkey(),
-        rent,
-        Mint::LEN as u64,
-        &token_program_id,
-    );
-
-    let init_mint_account = token_instruction::initialize_mint(
-        &token_program_id,
-        &mint_account_key.pubkey(),
-        &payer.pubkey(),
-        Some(&mint_account_key.pubkey()),
-        MINT_DECIMALS
-    );
-
-    let create_metadata_account = metadata_instruction::CreateMetadataAccountV3 {
-        metadata: metadata_account_address,
-        mint: mint_account_key.pubkey(),
-        mint_authority: payer.pubkey(),
-        payer: payer.pubkey(),
-        update_authority: (payer.pubkey(), true),
-        system_program: system_program::id(),
-        rent: None
+/// The account `set-authority` hands the authority to, `None` to revoke it.
+fn new_holder(new_authority: Option<Pubkey>, revoke: bool) -> Result<Option<Pubkey>> {
+    match (new_authority, revoke) {
+        (Some(new_authority), false) => Ok(Some(new_authority)),
+        (None, true) => Ok(None),
+        (Some(_), true) => bail!("--new-authority and --revoke can't be combined"),
+        (None, false) => bail!("Pass --new-authority, or --revoke to revoke the authority for good"),
     }
-    .instruction(CreateMetadataAccountV3InstructionArgs {
-        data: DataV2 {
-            name: "Test Token".to_string(),
-            symbol: "TT".to_string(),
-            uri: "https://raw.githubusercontent.com/hoang-nguyen-huy/fptu-solana-bootcamp/main/assets/tt-token.json".to_string(),
-            seller_fee_basis_points: 0,
-            creators: None,
-            collection: None, 
-            uses: None,
-        },
-        is_mutable: true, 
-        collection_details: None, 
-    });
-
-    let create_associated_token_account = instruction::create_associated_token_account(
-        &payer.pubkey(),
-        &payer.pubkey(),
-        &mint_account_key.pubkey(),
-        &token_program_id,
-    );
-
-    let mint_to_account = token_instruction::mint_to(
-        &token_program_id,
-        &mint_account_key.pubkey(),
-        &associated_token_account_address,
-        &payer.pubkey(),
-        &[&payer.pubkey()],
-        MINT_AMOUNT,
-    );
-
-    let mut instructions = Vec::with_capacity(5);
-    instructions.push(create_mint_account);
-    instructions.push(init_mint_account?);
-    instructions.push(create_metadata_account);
-
-    if client.get_account(&associated_token_account_address).is_err() {
-        instructions.push(create_associated_token_account);
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+
+    // metadata files are handled offline, without a cluster or a keypair
+    let command = match cli.command {
+        Command::Metadata(command) => return command.run(),
+        command => command,
+    };
+
+    let cluster = cli.cluster.resolve()?;
+    let client = cluster.rpc_client();
+
+    let payer = cli.keypair.read_keypair()?;
+    let context = Context {
+        client: &client,
+        cluster: &cluster,
+        payer: &payer,
+        simulate: cli.simulate.simulate,
+    };
+
+    match command {
+        Command::Create(args) => {
+            let mint_account_key = Keypair::new();
+
+            create::create_token(&context, &mint_account_key, args)?;
+        }
+        Command::Mint { mint, amount, recipient, authority } => {
+            let authority = read_authority(authority.as_deref())?;
+            let recipient = recipient.unwrap_or_else(|| payer.pubkey());
+
+            supply::mint_to(&context, authority.as_ref().unwrap_or(&payer), &mint, &recipient, &amount)?;
+        }
+        Command::Burn { mint, amount, owner } => {
+            let owner = read_authority(owner.as_deref())?;
+
+            supply::burn(&context, owner.as_ref().unwrap_or(&payer), &mint, &amount)?;
+        }
+        Command::Freeze { mint, owner, authority } => {
+            let authority = read_authority(authority.as_deref())?;
+
+            supply::set_frozen(&context, authority.as_ref().unwrap_or(&payer), &mint, &owner, true)?;
+        }
+        Command::Thaw { mint, owner, authority } => {
+            let authority = read_authority(authority.as_deref())?;
+
+            supply::set_frozen(&context, authority.as_ref().unwrap_or(&payer), &mint, &owner, false)?;
+        }
+        Command::SetAuthority { mint, kind, new_authority, revoke, authority } => {
+            let new_authority = new_holder(new_authority, revoke)?;
+            let authority = read_authority(authority.as_deref())?;
+
+            supply::set_authority(&context, authority.as_ref().unwrap_or(&payer), &mint, kind, new_authority.as_ref())?;
+        }
+        Command::MetadataAccount(command) => command.run(&context)?,
+        Command::Metadata(_) => unreachable!("handled before connecting"),
     }
-    instructions.push(mint_to_account?);
 
-    let create_blockhash = client.get_latest_blockhash()?;
-    let create_transaction = Transaction::new_signed_with_payer(
-        &instructions,
-        Some(&payer.pubkey()),
-        &[&payer, &mint_account_key],
-        create_blockhash,
-    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    let create_sig = client.send_and_confirm_transaction(&create_transaction)?;
+    fn parse(args: &[&str]) -> Result<Cli, clap::Error> {
+        Cli::try_parse_from(["mint_token"].iter().chain(args))
+    }
 
-    let explorer_url = util::get_signature_explorer_url(&create_sig.to_string());
+    fn set_authority(args: &[&str]) -> Result<(Option<Pubkey>, bool), clap::Error> {
+        let mint = Pubkey::new_unique().to_string();
+        let args: Vec<&str> = ["set-authority", mint.as_str(), "mint"].iter().chain(args).copied().collect();
 
-    println!("explorer url: {}", explorer_url);
+        match parse(&args)?.command {
+            Command::SetAuthority { new_authority, revoke, .. } => Ok((new_authority, revoke)),
+            _ => unreachable!("parsed another command"),
+        }
+    }
 
-//  https://explorer.solana.com/tx/4VhS75u2tDpxpXbT78kHWRQXjKNEMfHJzunH3LUKy1e5x48hXHnJq8zMkiU48cBmjLo4c1tURwZq7ze2kJqXZdiD?cluster=devnet
+    #[test]
+    fn set_authority_takes_a_new_authority_or_revoke() {
+        let new_authority = Pubkey::new_unique();
 
-    Ok(())
-}
+        let (parsed, revoke) = set_authority(&["--new-authority", &new_authority.to_string()]).unwrap();
+        assert_eq!(new_holder(parsed, revoke).unwrap(), Some(new_authority));
 
-fn main() -> Result<()> {
-    let rpc_url = "https://api.devnet.solana.com";
-    let client = RpcClient::new(rpc_url.to_string());
+        let (parsed, revoke) = set_authority(&["--revoke"]).unwrap();
+        assert_eq!(new_holder(parsed, revoke).unwrap(), None);
 
-    let payer = read_keypair_from_file("../payer-keypair.json");
-    let mint_account_key = Keypair::new();
+        assert!(set_authority(&[]).is_err());
+        assert!(set_authority(&["--revoke", "--new-authority", &new_authority.to_string()]).is_err());
+    }
 
-    mint_my_first_token(&client, &payer, &mint_account_key)?;
+    #[test]
+    fn new_holder_rejects_ambiguous_arguments() {
+        assert!(new_holder(Some(Pubkey::new_unique()), true).is_err());
+        assert!(new_holder(None, false).is_err());
+    }
 
-    Ok(())
+    #[test]
+    fn create_converts_supply_with_decimals() {
+        let supply = |args: &[&str]| match parse(args).unwrap().command {
+            Command::Create(args) => args.supply(),
+            _ => unreachable!("parsed another command"),
+        };
+
+        assert_eq!(supply(&["create"]).unwrap(), 100_000_000);
+        assert_eq!(supply(&["create", "--supply", "2.5", "--decimals", "2"]).unwrap(), 250);
+        assert_eq!(supply(&["create", "--supply", "0", "--decimals", "0"]).unwrap(), 0);
+        assert!(supply(&["create", "--supply", "2.555", "--decimals", "2"]).is_err());
+        assert!(supply(&["create", "--supply", "abc"]).is_err());
+    }
 }
@@ -0,0 +1,223 @@
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::{program_option::COption, signature::Keypair, signer::Signer};
+use spl_associated_token_account::get_associated_token_address_with_program_id;
+use spl_token_2022::extension::StateWithExtensions;
+use spl_token_2022::instruction::{self as token_instruction, AuthorityType};
+use spl_token_2022::state::{Account as TokenAccount, Mint};
+
+use anyhow::{anyhow, bail, Result};
+use bootcamp_common::{Context, TokenProgram};
+use clap::ValueEnum;
+
+/// Authority of a mint that `set-authority` can hand over or revoke.
+#[derive(Clone, Copy, Debug, ValueEnum)]
+pub enum MintAuthority {
+    /// Allowed to mint new tokens
+    Mint,
+    /// Allowed to freeze and thaw token accounts
+    Freeze,
+}
+
+impl MintAuthority {
+    fn authority_type(self) -> AuthorityType {
+        match self {
+            MintAuthority::Mint => AuthorityType::MintTokens,
+            MintAuthority::Freeze => AuthorityType::FreezeAccount,
+        }
+    }
+
+    fn current(self, mint: &Mint) -> COption<Pubkey> {
+        match self {
+            MintAuthority::Mint => mint.mint_authority,
+            MintAuthority::Freeze => mint.freeze_authority,
+        }
+    }
+}
+
+/// Fetches and decodes the mint at `mint`, along with the id of the token
+/// program owning it, either SPL Token or Token-2022.
+fn fetch_mint(client: &RpcClient, mint: &Pubkey) -> Result<(Pubkey, Mint)> {
+    let account = client.get_account(mint)?;
+
+    if TokenProgram::from_owner(&account.owner).is_none() {
+        bail!("Account {} is not owned by a token program", mint);
+    }
+
+    let state = StateWithExtensions::<Mint>::unpack(&account.data)
+        .map_err(|err| anyhow!("Account {} is not a mint: {}", mint, err))?;
+
+    Ok((account.owner, state.base))
+}
+
+/// Fails unless `signer` holds the `authority` of `mint`.
+fn check_authority(mint_address: &Pubkey, mint: &Mint, authority: MintAuthority, signer: &Pubkey) -> Result<()> {
+    match authority.current(mint) {
+        COption::Some(current) if current == *signer => Ok(()),
+        COption::Some(current) => bail!(
+            "The {:?} authority of mint {} is {}, not {}",
+            authority,
+            mint_address,
+            current,
+            signer
+        ),
+        COption::None => bail!("The {:?} authority of mint {} has been revoked", authority, mint_address),
+    }
+}
+
+/// Mints `amount` more whole tokens to the token account of `recipient`,
+/// creating it if needed.
+pub fn mint_to(
+    context: &Context,
+    authority: &Keypair,
+    mint_address: &Pubkey,
+    recipient: &Pubkey,
+    amount: &str,
+) -> Result<()> {
+    let (token_program_id, mint) = fetch_mint(context.client, mint_address)?;
+    check_authority(mint_address, &mint, MintAuthority::Mint, &authority.pubkey())?;
+
+    let amount = bootcamp_common::parse_ui_amount(amount, mint.decimals).map_err(|err| anyhow!("Invalid amount: {err}"))?;
+    let token_account = bootcamp_common::ensure_associated_token_account(
+        context.client,
+        &context.payer.pubkey(),
+        recipient,
+        mint_address,
+        &token_program_id,
+    )?;
+
+    let mut instructions: Vec<_> = token_account.create.into_iter().collect();
+    instructions.push(token_instruction::mint_to_checked(
+        &token_program_id,
+        mint_address,
+        &token_account.address,
+        &authority.pubkey(),
+        &[],
+        amount,
+        mint.decimals,
+    )?);
+
+    println!("minting {} base units to {}", amount, token_account.address);
+
+    context.send_transaction(&[authority], &instructions)?;
+
+    Ok(())
+}
+
+/// Burns `amount` whole tokens from the token account of `owner`.
+pub fn burn(
+    context: &Context,
+    owner: &Keypair,
+    mint_address: &Pubkey,
+    amount: &str,
+) -> Result<()> {
+    let (token_program_id, mint) = fetch_mint(context.client, mint_address)?;
+
+    let amount = bootcamp_common::parse_ui_amount(amount, mint.decimals).map_err(|err| anyhow!("Invalid amount: {err}"))?;
+    let token_account = get_associated_token_address_with_program_id(&owner.pubkey(), mint_address, &token_program_id);
+
+    let balance = StateWithExtensions::<TokenAccount>::unpack(&context.client.get_account(&token_account)?.data)?
+        .base
+        .amount;
+    if balance < amount {
+        bail!("Token account {} holds {} base units, can't burn {}", token_account, balance, amount);
+    }
+
+    let instructions = vec![token_instruction::burn_checked(
+        &token_program_id,
+        &token_account,
+        mint_address,
+        &owner.pubkey(),
+        &[],
+        amount,
+        mint.decimals,
+    )?];
+
+    println!("burning {} base units from {}", amount, token_account);
+
+    context.send_transaction(&[owner], &instructions)?;
+
+    Ok(())
+}
+
+/// Freezes the token account of `owner`, or thaws it when `freeze` is false.
+pub fn set_frozen(
+    context: &Context,
+    authority: &Keypair,
+    mint_address: &Pubkey,
+    owner: &Pubkey,
+    freeze: bool,
+) -> Result<()> {
+    let (token_program_id, mint) = fetch_mint(context.client, mint_address)?;
+    check_authority(mint_address, &mint, MintAuthority::Freeze, &authority.pubkey())?;
+
+    let token_account = get_associated_token_address_with_program_id(owner, mint_address, &token_program_id);
+
+    let instruction = if freeze {
+        token_instruction::freeze_account(&token_program_id, &token_account, mint_address, &authority.pubkey(), &[])?
+    } else {
+        token_instruction::thaw_account(&token_program_id, &token_account, mint_address, &authority.pubkey(), &[])?
+    };
+
+    println!("{} {}", if freeze { "freezing" } else { "thawing" }, token_account);
+
+    context.send_transaction(&[authority], &[instruction])?;
+
+    Ok(())
+}
+
+/// Hands the `kind` authority of the mint over to `new_authority`, or revokes
+/// it for good when `new_authority` is `None`.
+pub fn set_authority(
+    context: &Context,
+    authority: &Keypair,
+    mint_address: &Pubkey,
+    kind: MintAuthority,
+    new_authority: Option<&Pubkey>,
+) -> Result<()> {
+    let (token_program_id, mint) = fetch_mint(context.client, mint_address)?;
+    check_authority(mint_address, &mint, kind, &authority.pubkey())?;
+
+    let instruction = token_instruction::set_authority(
+        &token_program_id,
+        mint_address,
+        new_authority,
+        kind.authority_type(),
+        &authority.pubkey(),
+        &[],
+    )?;
+
+    match new_authority {
+        Some(new_authority) => println!("setting the {:?} authority of {} to {}", kind, mint_address, new_authority),
+        None => println!("revoking the {:?} authority of {} for good", kind, mint_address),
+    }
+
+    context.send_transaction(&[authority], &[instruction])?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn checks_the_current_authority() {
+        let (mint_address, authority) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let mint = Mint {
+            mint_authority: COption::Some(authority),
+            freeze_authority: COption::None,
+            decimals: 6,
+            is_initialized: true,
+            ..Mint::default()
+        };
+
+        assert!(check_authority(&mint_address, &mint, MintAuthority::Mint, &authority).is_ok());
+
+        let err = check_authority(&mint_address, &mint, MintAuthority::Mint, &Pubkey::new_unique()).unwrap_err();
+        assert!(err.to_string().contains(&authority.to_string()), "{err}");
+
+        let err = check_authority(&mint_address, &mint, MintAuthority::Freeze, &authority).unwrap_err();
+        assert!(err.to_string().contains("has been revoked"), "{err}");
+    }
+}
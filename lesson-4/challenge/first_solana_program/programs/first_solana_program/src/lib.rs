@@ -2,6 +2,9 @@ use anchor_lang::prelude::*;
 
 declare_id!("A1N7F27c9hpCWRV4oGYShxn1UvVq1duyvQQZwFmWWpPH");
 
+#[constant]
+pub const USER_DATA_SEED: &[u8] = b"user_data";
+
 #[program]
 pub mod first_solana_program {
     use super::*;
@@ -12,6 +15,7 @@ pub mod first_solana_program {
         }
 
         let user_data = &mut ctx.accounts.user_data;
+        user_data.owner = ctx.accounts.user.key();
         user_data.set_name(name)?;  
         user_data.age = age;
         Ok(())
@@ -33,12 +37,15 @@ pub mod first_solana_program {
 #[error_code]
 pub enum Error {
     #[msg("Name is too long")]
-    NameTooLong
+    NameTooLong,
+    #[msg("Only the owner can update this user data")]
+    NotOwner,
 }
 
 #[account]
 #[derive(InitSpace)]
 pub struct UserData {
+    owner: Pubkey,
     #[max_len(100)]
     name: String,
     age: u8,
@@ -56,7 +63,13 @@ impl UserData {
 
 #[derive(Accounts)]
 pub struct Store<'info> {
-    #[account(init , payer = user, space = 8 + UserData::INIT_SPACE)]
+    #[account(
+        init,
+        payer = user,
+        space = 8 + UserData::INIT_SPACE,
+        seeds = [USER_DATA_SEED, user.key().as_ref()],
+        bump
+    )]
     pub user_data: Account<'info, UserData>,
     #[account(mut)]
     pub user: Signer<'info>,
@@ -65,7 +78,8 @@ pub struct Store<'info> {
 
 #[derive(Accounts)]
 pub struct Update<'info> {
-    #[account(mut)]
+    #[account(mut, has_one = owner @ Error::NotOwner)]
     pub user_data: Account<'info, UserData>,
+    pub owner: Signer<'info>,
 }
 
@@ -1,16 +1,62 @@
 import * as anchor from "@coral-xyz/anchor";
 import { Program } from "@coral-xyz/anchor";
-import { FirstSolanaProgram } from "../target/deploy/first_solana_program";
+import { FirstSolanaProgram } from "../target/types/first_solana_program";
+import { assert, expect } from "chai";
 
 describe("first_solana_program", () => {
   // Configure the client to use the local cluster.
-  anchor.setProvider(anchor.AnchorProvider.env());
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
 
   const program = anchor.workspace.FirstSolanaProgram as Program<FirstSolanaProgram>;
 
+  const [userData] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("user_data"), provider.publicKey.toBytes()],
+    program.programId
+  );
+
   it("Is initialized!", async () => {
-    // Add your test here.
-    const tx = await program.methods.initialize().rpc();
+    const tx = await program.methods
+      .initialize("Khac Vy", 20)
+      .accounts({ user: provider.publicKey })
+      .rpc();
     console.log("Your transaction signature", tx);
+
+    const account = await program.account.userData.fetch(userData);
+    expect(account.owner.toBase58()).to.equal(provider.publicKey.toBase58());
+    expect(account.name).to.equal("Khac Vy");
+    expect(account.age).to.equal(20);
+  });
+
+  it("Is updated by the owner", async () => {
+    await program.methods
+      .update(null, 21)
+      .accountsPartial({ userData, owner: provider.publicKey })
+      .rpc();
+
+    const account = await program.account.userData.fetch(userData);
+    expect(account.name).to.equal("Khac Vy");
+    expect(account.age).to.equal(21);
+  });
+
+  it("Is not updated by anyone else", async () => {
+    const anotherUser = anchor.web3.Keypair.generate();
+
+    try {
+      await program.methods
+        .update("Mallory", null)
+        .accountsPartial({ userData, owner: anotherUser.publicKey })
+        .signers([anotherUser])
+        .rpc();
+
+      assert.ok(false);
+    } catch (_err) {
+      assert.isTrue(_err instanceof anchor.AnchorError);
+      const err: anchor.AnchorError = _err;
+      assert.strictEqual(err.error.errorCode.code, "NotOwner");
+    }
+
+    const account = await program.account.userData.fetch(userData);
+    expect(account.name).to.equal("Khac Vy");
   });
 });
@@ -2,26 +2,67 @@ use anchor_lang::prelude::*;
 
 declare_id!("3rF7YcxqiE1VEazdwvMNS4cswGYMYT9VWRVBbt1cMphW");
 
+#[constant]
+pub const COUNTER_SEED: &[u8] = b"counter";
+
 #[program]
 pub mod hello_world {
     use super::*;
 
-    pub fn initialize(_ctx: Context<Initialize>) -> Result<()> {
+    pub fn initialize(ctx: Context<Initialize>) -> Result<()> {
+        let counter = &mut ctx.accounts.counter;
+
+        counter.authority = ctx.accounts.payer.key();
+        counter.count = 0;
+
         Ok(())
     }
 
     pub fn increment(ctx: Context<Increment>) -> Result<()> {
         let counter = &mut ctx.accounts.counter;
 
-        counter.count = counter.count.checked_add(1).unwrap();
+        counter.count = counter.count.checked_add(1).ok_or(CounterError::Overflow)?;
         
         Ok(())
     }
+
+    pub fn decrement(ctx: Context<UpdateCounter>) -> Result<()> {
+        let counter = &mut ctx.accounts.counter;
+
+        counter.count = counter.count.checked_sub(1).ok_or(CounterError::Underflow)?;
+
+        Ok(())
+    }
+
+    pub fn reset(ctx: Context<UpdateCounter>) -> Result<()> {
+        ctx.accounts.counter.count = 0;
+
+        Ok(())
+    }
+
+    pub fn set(ctx: Context<UpdateCounter>, count: u64) -> Result<()> {
+        ctx.accounts.counter.count = count;
+
+        Ok(())
+    }
+}
+
+#[error_code]
+pub enum CounterError {
+    #[msg("Counter overflow")]
+    Overflow,
+
+    #[msg("Counter underflow")]
+    Underflow,
+
+    #[msg("Only the counter authority can do this")]
+    Unauthorized,
 }
 
 #[account]
 #[derive(InitSpace)]
 pub struct Counter {
+    authority: Pubkey,
     count: u64,
 }
 
@@ -33,7 +74,9 @@ pub struct Initialize<'info> {
     #[account(
         init,
         space = 8 + Counter::INIT_SPACE,
-        payer = payer
+        payer = payer,
+        seeds = [COUNTER_SEED, payer.key().as_ref()],
+        bump
     )]
     pub counter: Account<'info, Counter>,
     pub system_program: Program<'info, System>,
@@ -43,4 +86,12 @@ pub struct Initialize<'info> {
 pub struct Increment<'info> {
     #[account(mut)]
     pub counter: Account<'info, Counter>,
-}
\ No newline at end of file
+}
+
+#[derive(Accounts)]
+pub struct UpdateCounter<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(mut, has_one = authority @ CounterError::Unauthorized)]
+    pub counter: Account<'info, Counter>,
+}
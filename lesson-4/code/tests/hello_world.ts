@@ -11,52 +11,136 @@ describe("hello_world", () => {
 
   const program = anchor.workspace.HelloWorld as Program<HelloWorld>;
 
-  const counterKeypair = new anchor.web3.Keypair();
+  const [counter] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("counter"), payer.publicKey.toBytes()],
+    program.programId
+  );
 
   it("Is initialized!", async () => {
     await program.methods
       .initialize()
       .accounts({
-        counter: counterKeypair.publicKey,
+        counter,
         payer: payer.publicKey,
         systemProgram: anchor.web3.SystemProgram.programId,
       })
-      .signers([counterKeypair])
       .rpc();
 
-    const counterAccount = await program.account.counter.fetch(
-      counterKeypair.publicKey
-    );
+    const counterAccount = await program.account.counter.fetch(counter);
 
     assert(
       counterAccount.count.toNumber() === 0,
       "Expected initialized count to be 0"
     );
+    assert(
+      counterAccount.authority.equals(payer.publicKey),
+      "Expected payer to be the authority"
+    );
   });
 
   it("Increment Counter", async () => {
+    await program.methods.increment().accounts({ counter }).rpc();
+
+    const counterAccount = await program.account.counter.fetch(counter);
+
+    assert(counterAccount.count.toNumber() === 1, "Expected  count to be 1");
+  });
+
+  it("Increment Counter Again", async () => {
+    await program.methods.increment().accounts({ counter }).rpc();
+
+    const counterAccount = await program.account.counter.fetch(counter);
+
+    assert(counterAccount.count.toNumber() === 2, "Expected  count to be 2");
+  });
+
+  it("Decrement Counter", async () => {
     await program.methods
-      .increment()
-      .accounts({ counter: counterKeypair.publicKey })
+      .decrement()
+      .accounts({ counter, authority: payer.publicKey })
       .rpc();
 
-    const counterAccount = await program.account.counter.fetch(
-      counterKeypair.publicKey
-    );
+    const counterAccount = await program.account.counter.fetch(counter);
 
     assert(counterAccount.count.toNumber() === 1, "Expected  count to be 1");
   });
 
-  it("Increment Counter Again", async () => {
+  it("Set Counter", async () => {
     await program.methods
-      .increment()
-      .accounts({ counter: counterKeypair.publicKey })
+      .set(new anchor.BN(42))
+      .accounts({ counter, authority: payer.publicKey })
       .rpc();
 
-    const counterAccount = await program.account.counter.fetch(
-      counterKeypair.publicKey
-    );
+    const counterAccount = await program.account.counter.fetch(counter);
 
-    assert(counterAccount.count.toNumber() === 2, "Expected  count to be 2");
+    assert(counterAccount.count.toNumber() === 42, "Expected  count to be 42");
+  });
+
+  it("Set Counter by another authority fails", async () => {
+    const anotherAuthority = new anchor.web3.Keypair();
+
+    try {
+      await program.methods
+        .set(new anchor.BN(0))
+        .accounts({ counter, authority: anotherAuthority.publicKey })
+        .signers([anotherAuthority])
+        .rpc();
+
+      assert(false, "Expected set to fail");
+    } catch (err) {
+      assert(err instanceof anchor.AnchorError, "Expected an AnchorError");
+      assert(err.error.errorCode.code === "Unauthorized");
+    }
+  });
+
+  it("Increment Counter past u64::MAX fails", async () => {
+    const max = new anchor.BN("18446744073709551615");
+
+    await program.methods
+      .set(max)
+      .accounts({ counter, authority: payer.publicKey })
+      .rpc();
+
+    try {
+      await program.methods.increment().accounts({ counter }).rpc();
+
+      assert(false, "Expected increment to fail");
+    } catch (err) {
+      assert(err instanceof anchor.AnchorError, "Expected an AnchorError");
+      assert(err.error.errorCode.code === "Overflow");
+    }
+
+    const counterAccount = await program.account.counter.fetch(counter);
+
+    assert(counterAccount.count.eq(max), "Expected count to stay at u64::MAX");
+  });
+
+  it("Reset Counter", async () => {
+    await program.methods
+      .reset()
+      .accounts({ counter, authority: payer.publicKey })
+      .rpc();
+
+    const counterAccount = await program.account.counter.fetch(counter);
+
+    assert(counterAccount.count.toNumber() === 0, "Expected  count to be 0");
+  });
+
+  it("Decrement Counter below zero fails", async () => {
+    try {
+      await program.methods
+        .decrement()
+        .accounts({ counter, authority: payer.publicKey })
+        .rpc();
+
+      assert(false, "Expected decrement to fail");
+    } catch (err) {
+      assert(err instanceof anchor.AnchorError, "Expected an AnchorError");
+      assert(err.error.errorCode.code === "Underflow");
+    }
+
+    const counterAccount = await program.account.counter.fetch(counter);
+
+    assert(counterAccount.count.toNumber() === 0, "Expected count to stay 0");
   });
 });
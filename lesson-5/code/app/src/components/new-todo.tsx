@@ -38,12 +38,19 @@ export default function NewTodo({
   const provider = useAnchorProvider();
 
   const { isPending, mutateAsync } = useMutation({
-    mutationKey: ["create-todo", provider.publicKey, profile.todoCount],
+    mutationKey: [
+      "create-todo",
+      provider.publicKey,
+      profile.nextTodoIndex.toNumber(),
+    ],
     mutationFn: async (content: string) => {
       try {
         const program = new TodoProgram(provider);
 
-        const tx = await program.createTodo(content, profile.todoCount);
+        const tx = await program.createTodo(
+          content,
+          profile.nextTodoIndex.toNumber()
+        );
         const signature = await provider.sendAndConfirm(tx);
 
         return signature;
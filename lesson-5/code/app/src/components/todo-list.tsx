@@ -16,7 +16,11 @@ export default function TodoList({
   const provider = useAnchorProvider();
 
   const { data: todos, isLoading } = useQuery({
-    queryKey: ["todos", profile.key.toBase58(), profile.todoCount],
+    queryKey: [
+      "todos",
+      profile.key.toBase58(),
+      profile.nextTodoIndex.toNumber(),
+    ],
     enabled: !!profile,
     queryFn: () => new TodoProgram(provider).fetchTodos(profile),
   });
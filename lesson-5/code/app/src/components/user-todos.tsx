@@ -33,7 +33,7 @@ export default function UserTodos() {
     return <NewProfile />;
   }
 
-  console.log("profile", profile.todoCount);
+  console.log("profile", profile.todoCount.toNumber());
 
   return (
     <Flex direction="column" gap={8}>
@@ -1,9 +1,14 @@
+import { BN } from "@coral-xyz/anchor";
 import { Cluster, PublicKey } from "@solana/web3.js";
 
 export const TODO_PROGRAM_ID = new PublicKey(
   "79Vi6MSmxMrgv9oWqEYbijN8j9gZacbvZHo9nqZQFFxv"
 );
 
+// Discriminator plus `Profile::SPACE`. Profiles still in the legacy layout are
+// smaller and can't be decoded until they are migrated.
+export const PROFILE_ACCOUNT_SIZE = 8 + 32 + (4 + 100) + 32 + 8 + 8 + 8;
+
 export function getProgramId(cluster: Cluster) {
   switch (cluster) {
     case "devnet":
@@ -13,3 +18,15 @@ export function getProgramId(cluster: Cluster) {
       return TODO_PROGRAM_ID;
   }
 }
+
+// Minimal little-endian encoding of a todo index, matching `Todo::index_seed`.
+export function todoIndexSeed(index: number) {
+  const bytes = new BN(index).toArrayLike(Buffer, "le", 8);
+
+  let len = bytes.length;
+  while (len > 1 && bytes[len - 1] === 0) {
+    len--;
+  }
+
+  return bytes.subarray(0, len);
+}
@@ -1,11 +1,26 @@
-import { AnchorProvider, IdlAccounts, Program, utils } from "@coral-xyz/anchor";
+import {
+  AnchorProvider,
+  BN,
+  IdlAccounts,
+  Program,
+  utils,
+} from "@coral-xyz/anchor";
 import { TodoApp, IDL } from "../../../target/types/todo_app";
 import { Cluster, PublicKey, SystemProgram } from "@solana/web3.js";
-import { getProgramId } from "./helper";
+import { PROFILE_ACCOUNT_SIZE, getProgramId, todoIndexSeed } from "./helper";
+
+export type TodoPriority = "low" | "medium" | "high";
+
+export type TodoOptions = {
+  dueAt?: number; // unix timestamp in seconds
+  priority?: TodoPriority;
+  tags?: string[];
+};
 
 export default class TodoProgram {
   program: Program<TodoApp>;
   provider: AnchorProvider;
+  private profile?: Promise<PublicKey>;
 
   constructor(provider: AnchorProvider, cluster: Cluster = "devnet") {
     this.provider = provider;
@@ -28,52 +43,150 @@ export default class TodoProgram {
     return builder.transaction();
   }
 
-  fetchProfile() {
-    const [profile] = PublicKey.findProgramAddressSync(
+  // A profile stays at the address seeded by its creator once its authority
+  // is transferred, so the signer's profile is looked up by authority. The
+  // authority follows the variable-length name, out of reach of a memcmp
+  // filter, hence the scan, which is limited to migrated profiles and done
+  // once per instance.
+  findProfile() {
+    this.profile ??= this.lookUpProfile().catch((error) => {
+      this.profile = undefined;
+      throw error;
+    });
+
+    return this.profile;
+  }
+
+  private async lookUpProfile() {
+    const [own] = PublicKey.findProgramAddressSync(
       [utils.bytes.utf8.encode("profile"), this.provider.publicKey.toBytes()],
       this.program.programId
     );
 
-    return this.program.account.profile.fetch(profile);
+    const ownAccount = await this.program.account.profile.fetchNullable(own);
+    if (ownAccount?.authority.equals(this.provider.publicKey)) {
+      return own;
+    }
+
+    const profiles = await this.program.account.profile.all([
+      { dataSize: PROFILE_ACCOUNT_SIZE },
+    ]);
+    const received = profiles.find(({ account }) =>
+      account.authority.equals(this.provider.publicKey)
+    );
+
+    return received?.publicKey ?? own;
   }
 
-  createTodo(content: string, todoIndex: number) {
-    const [profile] = PublicKey.findProgramAddressSync(
-      [Buffer.from("profile"), this.provider.publicKey.toBytes()],
+  async fetchProfile() {
+    return this.program.account.profile.fetch(await this.findProfile());
+  }
+
+  async createTodo(
+    content: string,
+    todoIndex: number,
+    { dueAt, priority = "medium", tags = [] }: TodoOptions = {}
+  ) {
+    const profile = await this.findProfile();
+
+    const [todo] = PublicKey.findProgramAddressSync(
+      [Buffer.from("todo"), profile.toBytes(), todoIndexSeed(todoIndex)],
       this.program.programId
     );
 
+    const builder = this.program.methods
+      .createTodo(
+        content,
+        dueAt === undefined ? null : new BN(dueAt),
+        { [priority]: {} } as never,
+        tags
+      )
+      .accounts({
+        creator: this.provider.publicKey,
+        collaborator: null,
+        profile,
+        todo,
+        systemProgram: SystemProgram.programId,
+      });
+
+    return builder.transaction();
+  }
+
+  async toggleTodo(todoIndex: number) {
+    const profile = await this.findProfile();
+
     const [todo] = PublicKey.findProgramAddressSync(
-      [Buffer.from("todo"), profile.toBytes(), Buffer.from([todoIndex])],
+      [Buffer.from("todo"), profile.toBytes(), todoIndexSeed(todoIndex)],
       this.program.programId
     );
 
-    const builder = this.program.methods.createTodo(content).accounts({
-      creator: this.provider.publicKey,
+    const builder = this.program.methods.toggleTodo(new BN(todoIndex)).accounts({
+      authority: this.provider.publicKey,
+      collaborator: null,
+      profile,
+      todo,
+    });
+
+    return builder.transaction();
+  }
+
+  async updateTodo(todoIndex: number, content: string) {
+    const profile = await this.findProfile();
+
+    const [todo] = PublicKey.findProgramAddressSync(
+      [Buffer.from("todo"), profile.toBytes(), todoIndexSeed(todoIndex)],
+      this.program.programId
+    );
+
+    const builder = this.program.methods
+      .updateTodo(new BN(todoIndex), content, null, null)
+      .accounts({
+        authority: this.provider.publicKey,
+        collaborator: null,
+        profile,
+        todo,
+      });
+
+    return builder.transaction();
+  }
+
+  async deleteTodo(todoIndex: number) {
+    const profile = await this.findProfile();
+
+    const [todo] = PublicKey.findProgramAddressSync(
+      [Buffer.from("todo"), profile.toBytes(), todoIndexSeed(todoIndex)],
+      this.program.programId
+    );
+
+    const builder = this.program.methods.deleteTodo(new BN(todoIndex)).accounts({
+      authority: this.provider.publicKey,
+      collaborator: null,
       profile,
       todo,
-      systemProgram: SystemProgram.programId,
     });
 
     return builder.transaction();
   }
 
   async fetchTodos(profile: IdlAccounts<typeof IDL>["profile"]) {
-    const todoCount = profile.todoCount;
+    const nextTodoIndex = profile.nextTodoIndex.toNumber();
 
     const todoPdas: PublicKey[] = [];
 
-    for (let i = 0; i < todoCount; i++) {
+    for (let i = 0; i < nextTodoIndex; i++) {
       const [todo] = PublicKey.findProgramAddressSync(
-        [Buffer.from("todo"), profile.key.toBytes(), Buffer.from([i])],
+        [Buffer.from("todo"), profile.key.toBytes(), todoIndexSeed(i)],
         this.program.programId
       );
 
       todoPdas.push(todo);
     }
 
-    return Promise.all(
-      todoPdas.map((pda) => this.program.account.todo.fetch(pda))
+    // deleted todos leave gaps in the index range, so skip closed accounts
+    const todos = await this.program.account.todo.fetchMultiple(todoPdas);
+
+    return todos.filter(
+      (todo): todo is NonNullable<typeof todo> => todo !== null
     );
   }
 }
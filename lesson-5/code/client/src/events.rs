@@ -0,0 +1,137 @@
+//! Decoding of the events `todo_app` emits, out of transaction logs.
+
+use anchor_lang::{Discriminator, Event};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use todo_app::events::*;
+
+use crate::PROGRAM_ID;
+
+const PROGRAM_DATA: &str = "Program data: ";
+
+/// Any event emitted by `todo_app`, as decoded by [`parse_events`].
+#[derive(Debug, Clone)]
+pub enum TodoAppEvent {
+    ProfileCreated(ProfileCreated),
+    ProfileUpdated(ProfileUpdated),
+    ProfileClosed(ProfileClosed),
+    TodoCreated(TodoCreated),
+    TodoUpdated(TodoUpdated),
+    TodoToggled(TodoToggled),
+    TodoDeleted(TodoDeleted),
+    CollaboratorInvited(CollaboratorInvited),
+    CollaboratorRevoked(CollaboratorRevoked),
+}
+
+/// Decodes the `todo_app` events out of the log messages of a transaction.
+///
+/// Only `Program data:` lines logged while `todo_app` itself is the running
+/// program are considered, so data logged by other programs, including the
+/// ones `todo_app` calls through CPI, is skipped. Lines that fail to decode
+/// are ignored.
+pub fn parse_events<S: AsRef<str>>(logs: &[S]) -> Vec<TodoAppEvent> {
+    let invoke = format!("Program {PROGRAM_ID} invoke");
+
+    let mut call_stack: Vec<bool> = Vec::new();
+    let mut events = Vec::new();
+
+    for log in logs {
+        let log = log.as_ref();
+
+        if let Some(data) = log.strip_prefix(PROGRAM_DATA) {
+            if call_stack.last() == Some(&true) {
+                events.extend(decode_event(data));
+            }
+        } else if log.starts_with("Program ") && log.contains(" invoke [") {
+            call_stack.push(log.starts_with(&invoke));
+        } else if log.starts_with("Program ") && (log.ends_with(" success") || log.contains(" failed: ")) {
+            call_stack.pop();
+        }
+    }
+
+    events
+}
+
+fn decode_event(data: &str) -> Option<TodoAppEvent> {
+    let bytes = STANDARD.decode(data).ok()?;
+
+    if bytes.len() < 8 {
+        return None;
+    }
+
+    let (discriminator, mut data) = bytes.split_at(8);
+
+    fn decode<E: Event>(data: &mut &[u8]) -> Option<E> {
+        E::deserialize(data).ok()
+    }
+
+    match discriminator {
+        d if d == ProfileCreated::DISCRIMINATOR => decode(&mut data).map(TodoAppEvent::ProfileCreated),
+        d if d == ProfileUpdated::DISCRIMINATOR => decode(&mut data).map(TodoAppEvent::ProfileUpdated),
+        d if d == ProfileClosed::DISCRIMINATOR => decode(&mut data).map(TodoAppEvent::ProfileClosed),
+        d if d == TodoCreated::DISCRIMINATOR => decode(&mut data).map(TodoAppEvent::TodoCreated),
+        d if d == TodoUpdated::DISCRIMINATOR => decode(&mut data).map(TodoAppEvent::TodoUpdated),
+        d if d == TodoToggled::DISCRIMINATOR => decode(&mut data).map(TodoAppEvent::TodoToggled),
+        d if d == TodoDeleted::DISCRIMINATOR => decode(&mut data).map(TodoAppEvent::TodoDeleted),
+        d if d == CollaboratorInvited::DISCRIMINATOR => decode(&mut data).map(TodoAppEvent::CollaboratorInvited),
+        d if d == CollaboratorRevoked::DISCRIMINATOR => decode(&mut data).map(TodoAppEvent::CollaboratorRevoked),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{todo_address, Priority};
+    use solana_sdk::{pubkey::Pubkey, system_program};
+
+    const CONTENT: &str = "Do Solana bootcamp homework";
+
+    fn program_data(event: &impl Event) -> String {
+        format!("{PROGRAM_DATA}{}", STANDARD.encode(event.data()))
+    }
+
+    #[test]
+    fn parse_events_only_from_todo_app() {
+        let profile = Pubkey::new_unique();
+        let other_program = Pubkey::new_unique();
+
+        let todo_created = TodoCreated {
+            profile,
+            todo: todo_address(&profile, 0).0,
+            index: 0,
+            content: CONTENT.to_string(),
+            due_at: None,
+            priority: Priority::Low,
+            tags: vec![],
+        };
+        let profile_created = ProfileCreated {
+            profile,
+            authority: Pubkey::new_unique(),
+            name: "Khac Vy".to_string(),
+        };
+
+        let logs = vec![
+            format!("Program {PROGRAM_ID} invoke [1]"),
+            "Program log: Instruction: CreateTodo".to_string(),
+            format!("Program {} invoke [2]", system_program::ID),
+            program_data(&profile_created),
+            format!("Program {} success", system_program::ID),
+            program_data(&todo_created),
+            "Program data: not base64".to_string(),
+            format!("Program {PROGRAM_ID} consumed 12345 of 200000 compute units"),
+            format!("Program {PROGRAM_ID} success"),
+            format!("Program {other_program} invoke [1]"),
+            program_data(&profile_created),
+            format!("Program {other_program} success"),
+        ];
+
+        match parse_events(&logs).as_slice() {
+            [TodoAppEvent::TodoCreated(event)] => {
+                assert_eq!(event.profile, profile);
+                assert_eq!(event.index, 0);
+                assert_eq!(event.content, CONTENT);
+            }
+            events => panic!("unexpected events {events:?}"),
+        }
+    }
+}
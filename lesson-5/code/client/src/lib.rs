@@ -0,0 +1,507 @@
+//! Rust client for the `todo_app` program: PDA derivation, instruction builders,
+//! account fetching and event parsing, built on the types the program crate
+//! already exports.
+
+use anchor_lang::{AccountDeserialize, AnchorDeserialize, Discriminator, InstructionData, ToAccountMetas};
+use anyhow::{anyhow, bail, Context, Result};
+use solana_account_decoder::UiAccountEncoding;
+use solana_client::rpc_client::RpcClient;
+use solana_client::rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig};
+use solana_client::rpc_filter::{Memcmp, RpcFilterType};
+use solana_sdk::{instruction::Instruction, pubkey::Pubkey, system_program};
+use todo_app::constant::{COLLABORATOR_SEED, PROFILE_SEED, TODO_SEED};
+
+pub use events::{parse_events, TodoAppEvent};
+pub use todo_app::state::{Collaborator, LegacyProfile, LegacyTodo, Priority, Profile, Role, Todo};
+pub use todo_app::ID as PROGRAM_ID;
+
+mod events;
+
+/// `getMultipleAccounts` accepts at most 100 keys per request.
+const MAX_MULTIPLE_ACCOUNTS: usize = 100;
+
+pub fn profile_address(creator: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[PROFILE_SEED, creator.as_ref()], &PROGRAM_ID)
+}
+
+pub fn todo_address(profile: &Pubkey, index: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[TODO_SEED, profile.as_ref(), &Todo::index_seed(index)],
+        &PROGRAM_ID,
+    )
+}
+
+pub fn collaborator_address(profile: &Pubkey, member: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[COLLABORATOR_SEED, profile.as_ref(), member.as_ref()],
+        &PROGRAM_ID,
+    )
+}
+
+/// The collaborator account `signer` passes to prove its role on `profile`,
+/// `None` when signing as the profile authority.
+fn signer_collaborator(profile: &Pubkey, signer: &Pubkey, as_collaborator: bool) -> Option<Pubkey> {
+    as_collaborator.then(|| collaborator_address(profile, signer).0)
+}
+
+pub fn create_profile(creator: &Pubkey, name: &str) -> Instruction {
+    let (profile, _) = profile_address(creator);
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: todo_app::accounts::CreateProfile {
+            creator: *creator,
+            profile,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: todo_app::instruction::CreateProfile {
+            name: name.to_string(),
+        }
+        .data(),
+    }
+}
+
+/// Builds `create_todo` for the todo at `index`, which must be the profile's
+/// current `next_todo_index`. Set `as_collaborator` when `creator` is an editor
+/// rather than the profile authority.
+#[allow(clippy::too_many_arguments)]
+pub fn create_todo(
+    creator: &Pubkey,
+    as_collaborator: bool,
+    profile: &Pubkey,
+    index: u64,
+    content: &str,
+    due_at: Option<i64>,
+    priority: Priority,
+    tags: Vec<String>,
+) -> Instruction {
+    let (todo, _) = todo_address(profile, index);
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: todo_app::accounts::CreateTodo {
+            creator: *creator,
+            collaborator: signer_collaborator(profile, creator, as_collaborator),
+            profile: *profile,
+            todo,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: todo_app::instruction::CreateTodo {
+            content: content.to_string(),
+            due_at,
+            priority,
+            tags,
+        }
+        .data(),
+    }
+}
+
+pub fn toggle_todo(authority: &Pubkey, as_collaborator: bool, profile: &Pubkey, index: u64) -> Instruction {
+    let (todo, _) = todo_address(profile, index);
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: todo_app::accounts::ToggleTodo {
+            authority: *authority,
+            collaborator: signer_collaborator(profile, authority, as_collaborator),
+            profile: *profile,
+            todo,
+        }
+        .to_account_metas(None),
+        data: todo_app::instruction::ToggleTodo { index }.data(),
+    }
+}
+
+/// Builds `invite_collaborator`, signed by the profile authority or, with
+/// `as_collaborator`, by an admin.
+pub fn invite_collaborator(
+    inviter: &Pubkey,
+    as_collaborator: bool,
+    profile: &Pubkey,
+    member: &Pubkey,
+    role: Role,
+) -> Instruction {
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: todo_app::accounts::InviteCollaborator {
+            inviter: *inviter,
+            inviter_collaborator: signer_collaborator(profile, inviter, as_collaborator),
+            profile: *profile,
+            collaborator: collaborator_address(profile, member).0,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: todo_app::instruction::InviteCollaborator {
+            member: *member,
+            role,
+        }
+        .data(),
+    }
+}
+
+/// Builds `revoke_collaborator`. `invited_by` is the `Collaborator::invited_by`
+/// of the revoked member, which gets the rent back.
+pub fn revoke_collaborator(
+    revoker: &Pubkey,
+    as_collaborator: bool,
+    profile: &Pubkey,
+    member: &Pubkey,
+    invited_by: &Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: todo_app::accounts::RevokeCollaborator {
+            revoker: *revoker,
+            revoker_collaborator: signer_collaborator(profile, revoker, as_collaborator),
+            profile: *profile,
+            invited_by: *invited_by,
+            collaborator: collaborator_address(profile, member).0,
+        }
+        .to_account_metas(None),
+        data: todo_app::instruction::RevokeCollaborator { member: *member }.data(),
+    }
+}
+
+/// A profile account in either layout.
+pub enum ProfileAccount {
+    Current(Profile),
+
+    /// still in the legacy layout, which no instruction but `migrate_profile`
+    /// accepts
+    Legacy(LegacyProfile),
+}
+
+impl ProfileAccount {
+    pub fn authority(&self) -> Pubkey {
+        match self {
+            ProfileAccount::Current(profile) => profile.authority,
+            ProfileAccount::Legacy(profile) => profile.authority,
+        }
+    }
+}
+
+/// Decodes a profile account in the current layout, or in the legacy one when
+/// it was never migrated.
+pub fn decode_profile(data: &[u8]) -> Result<ProfileAccount> {
+    // like todos, the layouts are told apart by size as `migrate_profile` does
+    if data.len() != 8 + LegacyProfile::SPACE {
+        return Ok(ProfileAccount::Current(Profile::try_deserialize(&mut &data[..])?));
+    }
+
+    if data[..8] != Profile::DISCRIMINATOR {
+        bail!("Not a profile account");
+    }
+
+    Ok(ProfileAccount::Legacy(LegacyProfile::deserialize(&mut &data[8..])?))
+}
+
+/// Fetches a profile in the current layout, failing with a hint to run
+/// `migrate_profile` when it is still a legacy one.
+pub fn fetch_profile(client: &RpcClient, address: &Pubkey) -> Result<Profile> {
+    let data = client
+        .get_account_data(address)
+        .with_context(|| format!("Unable to fetch profile {address}"))?;
+
+    match decode_profile(&data).with_context(|| format!("Unable to decode profile {address}"))? {
+        ProfileAccount::Current(profile) => Ok(profile),
+        ProfileAccount::Legacy(_) => bail!("Profile {address} is in the legacy layout, run `migrate_profile` first"),
+    }
+}
+
+/// Lists the profiles `authority` controls, with their address. A profile stays
+/// at the address seeded by its creator after a transfer, so it can't be
+/// derived from `authority`; and the authority sits after the variable-length
+/// name, out of reach of a `memcmp` filter, so profiles are matched here.
+/// Profiles in the legacy layout are listed too, as they need `migrate_profile`
+/// before anything else.
+pub fn find_profiles(client: &RpcClient, authority: &Pubkey) -> Result<Vec<(Pubkey, ProfileAccount)>> {
+    let config = RpcProgramAccountsConfig {
+        // both layouts share the discriminator
+        filters: Some(vec![RpcFilterType::Memcmp(Memcmp::new_base58_encoded(
+            0,
+            &Profile::DISCRIMINATOR,
+        ))]),
+        account_config: RpcAccountInfoConfig {
+            encoding: Some(UiAccountEncoding::Base64),
+            ..RpcAccountInfoConfig::default()
+        },
+        ..RpcProgramAccountsConfig::default()
+    };
+
+    let accounts = client
+        .get_program_accounts_with_config(&PROGRAM_ID, config)
+        .context("Unable to fetch profiles")?;
+
+    let mut profiles = Vec::new();
+
+    for (address, account) in accounts {
+        let profile =
+            decode_profile(&account.data).with_context(|| format!("Unable to decode profile {address}"))?;
+
+        if profile.authority() == *authority {
+            profiles.push((address, profile));
+        }
+    }
+
+    Ok(profiles)
+}
+
+pub fn fetch_todo(client: &RpcClient, address: &Pubkey) -> Result<Todo> {
+    let data = client
+        .get_account_data(address)
+        .with_context(|| format!("Unable to fetch todo {address}"))?;
+
+    Todo::try_deserialize(&mut data.as_slice())
+        .with_context(|| format!("Unable to decode todo {address}"))
+}
+
+pub fn fetch_collaborator(client: &RpcClient, address: &Pubkey) -> Result<Collaborator> {
+    let data = client
+        .get_account_data(address)
+        .with_context(|| format!("Unable to fetch collaborator {address}"))?;
+
+    Collaborator::try_deserialize(&mut data.as_slice())
+        .with_context(|| format!("Unable to decode collaborator {address}"))
+}
+
+/// A todo listed by `fetch_todos`.
+pub struct TodoEntry {
+    pub index: u64,
+
+    pub address: Pubkey,
+
+    pub todo: Todo,
+
+    /// still in the legacy layout, read with the defaults `migrate_todo` would
+    /// fill in and zero timestamps
+    pub legacy: bool,
+}
+
+/// Decodes a todo account in the current layout, or in the legacy one when it
+/// was never migrated. Returns the todo and whether it was legacy.
+pub fn decode_todo(data: &[u8]) -> Result<(Todo, bool)> {
+    // the zero padding of a legacy todo also reads as a current one, so the
+    // layouts are told apart by size like `migrate_todo` does
+    if data.len() != 8 + LegacyTodo::SPACE {
+        return Ok((Todo::try_deserialize(&mut &data[..])?, false));
+    }
+
+    if data[..8] != Todo::DISCRIMINATOR {
+        bail!("Not a todo account");
+    }
+
+    let legacy = LegacyTodo::deserialize(&mut &data[8..])?;
+    let todo = Todo {
+        profile: legacy.profile,
+        content: legacy.content,
+        completed: legacy.completed,
+        due_at: None,
+        priority: Priority::Medium,
+        tags: Vec::new(),
+        created_at: 0,
+        updated_at: 0,
+        completed_at: None,
+    };
+
+    Ok((todo, true))
+}
+
+/// Lists the todos of `profile`, walking every index below `next_todo_index`.
+/// Deleted todos leave gaps and are skipped, legacy ones are decoded as such.
+pub fn fetch_todos(client: &RpcClient, profile: &Pubkey) -> Result<Vec<TodoEntry>> {
+    let profile_account = fetch_profile(client, profile)?;
+
+    let addresses: Vec<(u64, Pubkey)> = (0..profile_account.next_todo_index)
+        .map(|index| (index, todo_address(profile, index).0))
+        .collect();
+
+    let mut todos = Vec::with_capacity(profile_account.todo_count as usize);
+
+    for chunk in addresses.chunks(MAX_MULTIPLE_ACCOUNTS) {
+        let keys: Vec<Pubkey> = chunk.iter().map(|(_, address)| *address).collect();
+        let accounts = client
+            .get_multiple_accounts(&keys)
+            .context("Unable to fetch todos")?;
+
+        for ((index, address), account) in chunk.iter().zip(accounts) {
+            let Some(account) = account else {
+                continue;
+            };
+
+            let (todo, legacy) =
+                decode_todo(&account.data).map_err(|err| anyhow!("Unable to decode todo {address}: {err}"))?;
+
+            todos.push(TodoEntry {
+                index: *index,
+                address: *address,
+                todo,
+                legacy,
+            });
+        }
+    }
+
+    Ok(todos)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::{AccountSerialize, AnchorSerialize, Space};
+
+    fn todo(profile: Pubkey) -> Todo {
+        Todo {
+            profile,
+            content: "Do Solana bootcamp homework".to_string(),
+            completed: true,
+            due_at: Some(1_700_000_000),
+            priority: Priority::High,
+            tags: vec!["school".to_string()],
+            created_at: 1_600_000_000,
+            updated_at: 1_650_000_000,
+            completed_at: Some(1_650_000_000),
+        }
+    }
+
+    #[test]
+    fn addresses_follow_the_program_seeds() {
+        let creator = Pubkey::new_unique();
+        let member = Pubkey::new_unique();
+        let (profile, _) = profile_address(&creator);
+
+        assert_eq!(
+            profile,
+            Pubkey::find_program_address(&[b"profile", creator.as_ref()], &PROGRAM_ID).0
+        );
+        assert_eq!(
+            collaborator_address(&profile, &member).0,
+            Pubkey::find_program_address(&[COLLABORATOR_SEED, profile.as_ref(), member.as_ref()], &PROGRAM_ID).0
+        );
+
+        // indexes below 256 keep the single byte seed of the old `u8` counter
+        assert_eq!(
+            todo_address(&profile, 255).0,
+            Pubkey::find_program_address(&[b"todo", profile.as_ref(), &[255]], &PROGRAM_ID).0
+        );
+        assert_eq!(
+            todo_address(&profile, 256).0,
+            Pubkey::find_program_address(&[b"todo", profile.as_ref(), &[0, 1]], &PROGRAM_ID).0
+        );
+    }
+
+    #[test]
+    fn collaborator_only_when_signing_as_one() {
+        let profile = Pubkey::new_unique();
+        let signer = Pubkey::new_unique();
+
+        assert_eq!(signer_collaborator(&profile, &signer, false), None);
+        assert_eq!(
+            signer_collaborator(&profile, &signer, true),
+            Some(collaborator_address(&profile, &signer).0)
+        );
+    }
+
+    #[test]
+    fn decode_current_todo() {
+        let todo = todo(Pubkey::new_unique());
+        let mut data = Vec::new();
+        todo.try_serialize(&mut data).unwrap();
+        // accounts are allocated for the longest content and tags
+        data.resize(8 + Todo::INIT_SPACE, 0);
+
+        let (decoded, legacy) = decode_todo(&data).unwrap();
+
+        assert!(!legacy);
+        assert_eq!(decoded.content, todo.content);
+        assert_eq!(decoded.priority, Priority::High);
+        assert_eq!(decoded.tags, todo.tags);
+        assert_eq!(decoded.completed_at, todo.completed_at);
+    }
+
+    #[test]
+    fn decode_legacy_todo() {
+        let profile = Pubkey::new_unique();
+        let mut data = Todo::DISCRIMINATOR.to_vec();
+        LegacyTodo {
+            profile,
+            content: "Do Solana bootcamp homework".to_string(),
+            completed: true,
+        }
+        .serialize(&mut data)
+        .unwrap();
+        data.resize(8 + LegacyTodo::SPACE, 0);
+
+        let (decoded, legacy) = decode_todo(&data).unwrap();
+
+        assert!(legacy);
+        assert_eq!(decoded.profile, profile);
+        assert_eq!(decoded.content, "Do Solana bootcamp homework");
+        assert!(decoded.completed);
+        assert_eq!(decoded.priority, Priority::Medium);
+        assert!(decoded.tags.is_empty());
+    }
+
+    #[test]
+    fn decode_current_profile() {
+        let authority = Pubkey::new_unique();
+        let mut data = Vec::new();
+        Profile {
+            key: Pubkey::new_unique(),
+            name: "Khac Vy".to_string(),
+            authority,
+            todo_count: 1,
+            next_todo_index: 2,
+            collaborator_count: 0,
+        }
+        .try_serialize(&mut data)
+        .unwrap();
+        data.resize(8 + Profile::SPACE, 0);
+
+        match decode_profile(&data).unwrap() {
+            ProfileAccount::Current(profile) => {
+                assert_eq!(profile.authority, authority);
+                assert_eq!(profile.next_todo_index, 2);
+            }
+            ProfileAccount::Legacy(_) => panic!("decoded as legacy"),
+        }
+    }
+
+    #[test]
+    fn decode_legacy_profile() {
+        let authority = Pubkey::new_unique();
+        let mut data = Profile::DISCRIMINATOR.to_vec();
+        LegacyProfile {
+            key: Pubkey::new_unique(),
+            name: "Khac Vy".to_string(),
+            authority,
+            todo_count: 3,
+        }
+        .serialize(&mut data)
+        .unwrap();
+        data.resize(8 + LegacyProfile::SPACE, 0);
+
+        let profile = decode_profile(&data).unwrap();
+
+        assert_eq!(profile.authority(), authority);
+        match profile {
+            ProfileAccount::Legacy(profile) => assert_eq!(profile.todo_count, 3),
+            ProfileAccount::Current(_) => panic!("decoded as current"),
+        }
+    }
+
+    #[test]
+    fn decode_rejects_other_accounts() {
+        let mut data = Profile::DISCRIMINATOR.to_vec();
+        data.resize(8 + LegacyTodo::SPACE, 0);
+
+        assert!(decode_todo(&data).is_err());
+        assert!(decode_todo(&[]).is_err());
+
+        let mut data = Todo::DISCRIMINATOR.to_vec();
+        data.resize(8 + LegacyProfile::SPACE, 0);
+
+        assert!(decode_profile(&data).is_err());
+        assert!(decode_profile(&[]).is_err());
+    }
+}
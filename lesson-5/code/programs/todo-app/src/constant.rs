@@ -5,3 +5,12 @@ pub const PROFILE_SEED: &[u8] = b"profile";
 
 #[constant]
 pub const TODO_SEED: &[u8] = b"todo";
+
+#[constant]
+pub const PENDING_AUTHORITY_SEED: &[u8] = b"pending_authority";
+
+#[constant]
+pub const COLLABORATOR_SEED: &[u8] = b"collaborator";
+
+#[constant]
+pub const PROFILE_MIGRATION_SEED: &[u8] = b"profile_migration";
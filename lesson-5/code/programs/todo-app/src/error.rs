@@ -10,4 +10,46 @@ pub enum AppError {
 
     #[msg("Invalid authority")]
     InvalidAuthority,
+
+    #[msg("Todo count overflow")]
+    TodoCountOverflow,
+
+    #[msg("Profile is already migrated")]
+    ProfileAlreadyMigrated,
+
+    #[msg("Todo accounts do not match the todo indexes of the profile")]
+    TodoAccountsMismatch,
+
+    #[msg("Profile still has todos")]
+    ProfileHasTodos,
+
+    #[msg("Due date is in the past")]
+    DueDateInPast,
+
+    #[msg("Too many tags")]
+    TooManyTags,
+
+    #[msg("Tag is empty or too long")]
+    InvalidTag,
+
+    #[msg("Todo is already migrated")]
+    TodoAlreadyMigrated,
+
+    #[msg("Authority cannot be a collaborator")]
+    AuthorityAsCollaborator,
+
+    #[msg("Todo count underflow")]
+    TodoCountUnderflow,
+
+    #[msg("Profile still has collaborators")]
+    ProfileHasCollaborators,
+
+    #[msg("Collaborator count overflow")]
+    CollaboratorCountOverflow,
+
+    #[msg("Collaborator count underflow")]
+    CollaboratorCountUnderflow,
+
+    #[msg("Profile has a pending authority transfer")]
+    AuthorityTransferPending,
 }
@@ -0,0 +1,125 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{Priority, Role, Todo};
+
+#[event]
+#[derive(Debug, Clone)]
+pub struct ProfileCreated {
+    pub profile: Pubkey,
+
+    pub authority: Pubkey,
+
+    pub name: String,
+}
+
+#[event]
+#[derive(Debug, Clone)]
+pub struct ProfileUpdated {
+    pub profile: Pubkey,
+
+    pub authority: Pubkey,
+
+    pub name: String,
+}
+
+#[event]
+#[derive(Debug, Clone)]
+pub struct ProfileClosed {
+    pub profile: Pubkey,
+
+    pub authority: Pubkey,
+}
+
+#[event]
+#[derive(Debug, Clone)]
+pub struct TodoCreated {
+    pub profile: Pubkey,
+
+    pub todo: Pubkey,
+
+    pub index: u64,
+
+    pub content: String,
+
+    pub due_at: Option<i64>,
+
+    pub priority: Priority,
+
+    pub tags: Vec<String>,
+}
+
+#[event]
+#[derive(Debug, Clone)]
+pub struct TodoUpdated {
+    pub profile: Pubkey,
+
+    pub todo: Pubkey,
+
+    pub index: u64,
+
+    pub content: String,
+
+    pub due_at: Option<i64>,
+
+    pub priority: Priority,
+
+    pub tags: Vec<String>,
+}
+
+impl TodoUpdated {
+    pub fn new(todo: &Account<Todo>, index: u64) -> Self {
+        Self {
+            profile: todo.profile,
+            todo: todo.key(),
+            index,
+            content: todo.content.clone(),
+            due_at: todo.due_at,
+            priority: todo.priority,
+            tags: todo.tags.clone(),
+        }
+    }
+}
+
+#[event]
+#[derive(Debug, Clone)]
+pub struct TodoToggled {
+    pub profile: Pubkey,
+
+    pub todo: Pubkey,
+
+    pub index: u64,
+
+    pub completed: bool,
+}
+
+#[event]
+#[derive(Debug, Clone)]
+pub struct TodoDeleted {
+    pub profile: Pubkey,
+
+    pub todo: Pubkey,
+
+    pub index: u64,
+}
+
+#[event]
+#[derive(Debug, Clone)]
+pub struct CollaboratorInvited {
+    pub profile: Pubkey,
+
+    pub member: Pubkey,
+
+    pub role: Role,
+
+    pub invited_by: Pubkey,
+}
+
+#[event]
+#[derive(Debug, Clone)]
+pub struct CollaboratorRevoked {
+    pub profile: Pubkey,
+
+    pub member: Pubkey,
+
+    pub revoked_by: Pubkey,
+}
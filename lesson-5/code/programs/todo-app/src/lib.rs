@@ -1,13 +1,18 @@
 use anchor_lang::prelude::*;
+use anchor_lang::{system_program, Discriminator};
 use constant::*;
 use error::AppError;
-use state::{Profile, Todo};
+use events::*;
+use state::{
+    Collaborator, LegacyProfile, LegacyTodo, PendingAuthority, Priority, Profile, ProfileMigration, Role, Todo,
+};
 
 declare_id!("79Vi6MSmxMrgv9oWqEYbijN8j9gZacbvZHo9nqZQFFxv");
 
-mod constant;
-mod error;
-mod state;
+pub mod constant;
+pub mod error;
+pub mod events;
+pub mod state;
 
 #[program]
 pub mod todo_app {
@@ -24,14 +29,133 @@ pub mod todo_app {
         profile.name = name;
         profile.authority = ctx.accounts.creator.key();
         profile.todo_count = 0;
+        profile.next_todo_index = 0;
+        profile.collaborator_count = 0;
+
+        emit!(ProfileCreated {
+            profile: profile.key,
+            authority: profile.authority,
+            name: profile.name.clone(),
+        });
 
         Ok(())
     }
 
-    pub fn create_todo(ctx: Context<CreateTodo>, content: String) -> Result<()> {
-        if content.len() > 200 {
-            return err!(AppError::ContentTooLong);
-        }
+    pub fn update_profile(ctx: Context<UpdateProfile>, name: String) -> Result<()> {
+        require!(name.len() <= 100, AppError::NameTooLong);
+
+        let profile = &mut ctx.accounts.profile;
+
+        profile.name = name;
+
+        emit!(ProfileUpdated {
+            profile: profile.key(),
+            authority: profile.authority,
+            name: profile.name.clone(),
+        });
+
+        Ok(())
+    }
+
+    pub fn propose_authority(ctx: Context<ProposeAuthority>, new_authority: Pubkey) -> Result<()> {
+        let pending_authority = &mut ctx.accounts.pending_authority;
+
+        pending_authority.profile = ctx.accounts.profile.key();
+        pending_authority.proposed_by = ctx.accounts.authority.key();
+        pending_authority.new_authority = new_authority;
+
+        Ok(())
+    }
+
+    pub fn cancel_authority_transfer(_ctx: Context<CancelAuthorityTransfer>) -> Result<()> {
+        Ok(())
+    }
+
+    pub fn accept_authority(ctx: Context<AcceptAuthority>) -> Result<()> {
+        let profile = &mut ctx.accounts.profile;
+
+        profile.authority = ctx.accounts.new_authority.key();
+
+        emit!(ProfileUpdated {
+            profile: profile.key(),
+            authority: profile.authority,
+            name: profile.name.clone(),
+        });
+
+        Ok(())
+    }
+
+    pub fn close_profile(ctx: Context<CloseProfile>) -> Result<()> {
+        emit!(ProfileClosed {
+            profile: ctx.accounts.profile.key(),
+            authority: ctx.accounts.authority.key(),
+        });
+
+        Ok(())
+    }
+
+    pub fn invite_collaborator(
+        ctx: Context<InviteCollaborator>,
+        member: Pubkey,
+        role: Role,
+    ) -> Result<()> {
+        let profile = &mut ctx.accounts.profile;
+
+        require_keys_neq!(member, profile.authority, AppError::AuthorityAsCollaborator);
+
+        profile.collaborator_count = profile
+            .collaborator_count
+            .checked_add(1)
+            .ok_or(AppError::CollaboratorCountOverflow)?;
+
+        let collaborator = &mut ctx.accounts.collaborator;
+
+        collaborator.profile = profile.key();
+        collaborator.member = member;
+        collaborator.role = role;
+        collaborator.invited_by = ctx.accounts.inviter.key();
+
+        emit!(CollaboratorInvited {
+            profile: collaborator.profile,
+            member,
+            role,
+            invited_by: collaborator.invited_by,
+        });
+
+        Ok(())
+    }
+
+    /// Removes `member` from the profile. Besides the authority and admins,
+    /// members may revoke themselves to leave a shared profile.
+    pub fn revoke_collaborator(ctx: Context<RevokeCollaborator>, member: Pubkey) -> Result<()> {
+        let profile = &mut ctx.accounts.profile;
+
+        profile.collaborator_count = profile
+            .collaborator_count
+            .checked_sub(1)
+            .ok_or(AppError::CollaboratorCountUnderflow)?;
+
+        emit!(CollaboratorRevoked {
+            profile: ctx.accounts.profile.key(),
+            member,
+            revoked_by: ctx.accounts.revoker.key(),
+        });
+
+        Ok(())
+    }
+
+    pub fn create_todo(
+        ctx: Context<CreateTodo>,
+        content: String,
+        due_at: Option<i64>,
+        priority: Priority,
+        tags: Vec<String>,
+    ) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+
+        Todo::validate_content(&content)?;
+        Todo::validate_due_at(due_at, now)?;
+        Todo::validate_tags(&tags)?;
 
         let profile = &mut ctx.accounts.profile;
 
@@ -40,17 +164,289 @@ pub mod todo_app {
         todo.content = content;
         todo.profile = profile.key();
         todo.completed = false;
+        todo.due_at = due_at;
+        todo.priority = priority;
+        todo.tags = tags;
+        todo.created_at = now;
+        todo.updated_at = now;
+        todo.completed_at = None;
+
+        emit!(TodoCreated {
+            profile: profile.key(),
+            todo: todo.key(),
+            index: profile.next_todo_index,
+            content: todo.content.clone(),
+            due_at: todo.due_at,
+            priority: todo.priority,
+            tags: todo.tags.clone(),
+        });
 
-        profile.todo_count += 1;
+        profile.next_todo_index = profile
+            .next_todo_index
+            .checked_add(1)
+            .ok_or(AppError::TodoCountOverflow)?;
+        profile.todo_count = profile
+            .todo_count
+            .checked_add(1)
+            .ok_or(AppError::TodoCountOverflow)?;
 
         Ok(())
     }
 
-    pub fn toggle_todo(_ctx: Context<ToggleTodo>) -> Result<()> {
+    pub fn toggle_todo(ctx: Context<ToggleTodo>, index: u64) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+
+        let todo = &mut ctx.accounts.todo;
+
+        todo.completed = !todo.completed;
+        todo.completed_at = todo.completed.then_some(now);
+        todo.updated_at = now;
+
+        emit!(TodoToggled {
+            profile: todo.profile,
+            todo: todo.key(),
+            index,
+            completed: todo.completed,
+        });
+
+        Ok(())
+    }
+
+    pub fn update_todo(
+        ctx: Context<UpdateTodo>,
+        index: u64,
+        content: Option<String>,
+        priority: Option<Priority>,
+        tags: Option<Vec<String>>,
+    ) -> Result<()> {
+        let todo = &mut ctx.accounts.todo;
+
+        if let Some(content) = content {
+            Todo::validate_content(&content)?;
+            todo.content = content;
+        }
+        if let Some(priority) = priority {
+            todo.priority = priority;
+        }
+        if let Some(tags) = tags {
+            Todo::validate_tags(&tags)?;
+            todo.tags = tags;
+        }
+
+        todo.updated_at = Clock::get()?.unix_timestamp;
+
+        emit!(TodoUpdated::new(todo, index));
+
+        Ok(())
+    }
+
+    /// Sets or, with `None`, clears the due date of a todo.
+    pub fn set_due_date(ctx: Context<UpdateTodo>, index: u64, due_at: Option<i64>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+
+        Todo::validate_due_at(due_at, now)?;
+
+        let todo = &mut ctx.accounts.todo;
+
+        todo.due_at = due_at;
+        todo.updated_at = now;
+
+        emit!(TodoUpdated::new(todo, index));
+
+        Ok(())
+    }
+
+    pub fn delete_todo(ctx: Context<DeleteTodo>, index: u64) -> Result<()> {
+        // Only the live count goes down: `next_todo_index` is the seed of the
+        // next todo, so rewinding it would collide with a live index.
+        let profile = &mut ctx.accounts.profile;
+
+        profile.todo_count = profile
+            .todo_count
+            .checked_sub(1)
+            .ok_or(AppError::TodoCountUnderflow)?;
+
+        emit!(TodoDeleted {
+            profile: profile.key(),
+            todo: ctx.accounts.todo.key(),
+            index,
+        });
+
+        Ok(())
+    }
+
+    /// Upgrades a profile created with the `LegacyProfile` layout. The legacy
+    /// counter was never decremented on delete, so the live count is rebuilt
+    /// from the todo accounts of every index below it, passed in order as
+    /// remaining accounts. There can be more of them than fit in a transaction,
+    /// so they come in batches starting at `start`, each one continuing where
+    /// the previous one stopped, and the profile only takes the new layout with
+    /// the last one. Each account is checked against its PDA and an index
+    /// whose account no longer exists counts as deleted, so the count can't be
+    /// lowered by leaving live todos out.
+    pub fn migrate_profile<'info>(
+        ctx: Context<'_, '_, 'info, 'info, MigrateProfile<'info>>,
+        start: u64,
+    ) -> Result<()> {
+        let profile_info = ctx.accounts.profile.to_account_info();
+
+        require_keys_eq!(*profile_info.owner, crate::ID, ErrorCode::AccountOwnedByWrongProgram);
+
+        let legacy = {
+            let data = profile_info.try_borrow_data()?;
+
+            require!(data.len() == 8 + LegacyProfile::SPACE, AppError::ProfileAlreadyMigrated);
+            require!(data[..8] == Profile::DISCRIMINATOR, ErrorCode::AccountDiscriminatorMismatch);
+
+            LegacyProfile::deserialize(&mut &data[8..])?
+        };
+
+        require_keys_eq!(legacy.authority, ctx.accounts.authority.key(), AppError::InvalidAuthority);
+
+        let migration = &mut ctx.accounts.migration;
+
+        migration.profile = profile_info.key();
+
+        let end = start
+            .checked_add(ctx.remaining_accounts.len() as u64)
+            .ok_or(AppError::TodoAccountsMismatch)?;
+
+        require!(start == migration.next_index, AppError::TodoAccountsMismatch);
+        require!(end <= u64::from(legacy.todo_count), AppError::TodoAccountsMismatch);
+
+        for (index, todo_info) in (start..end).zip(ctx.remaining_accounts) {
+            let (address, _) = Pubkey::find_program_address(
+                &[TODO_SEED, profile_info.key.as_ref(), Todo::index_seed(index).as_ref()],
+                &crate::ID,
+            );
+
+            require_keys_eq!(todo_info.key(), address, AppError::TodoAccountsMismatch);
+
+            if todo_info.owner == &system_program::ID && todo_info.data_is_empty() {
+                continue; // deleted
+            }
+
+            // todos may still be in the legacy layout, whose fields prefix the current one
+            require_keys_eq!(*todo_info.owner, crate::ID, ErrorCode::AccountOwnedByWrongProgram);
+
+            let todo = {
+                let data = todo_info.try_borrow_data()?;
+
+                require!(data.len() >= 8 && data[..8] == Todo::DISCRIMINATOR, ErrorCode::AccountDiscriminatorMismatch);
+
+                LegacyTodo::deserialize(&mut &data[8..])?
+            };
+
+            require_keys_eq!(todo.profile, profile_info.key(), AppError::InvalidAuthority);
+
+            migration.todo_count += 1;
+        }
+
+        migration.next_index = end;
+
+        if end < u64::from(legacy.todo_count) {
+            return Ok(());
+        }
+
+        grow_account(
+            &profile_info,
+            &ctx.accounts.authority,
+            &ctx.accounts.system_program,
+            8 + Profile::SPACE,
+        )?;
+
+        let profile = Profile {
+            key: legacy.key,
+            name: legacy.name,
+            authority: legacy.authority,
+            todo_count: migration.todo_count,
+            next_todo_index: u64::from(legacy.todo_count),
+            collaborator_count: 0,
+        };
+
+        {
+            let mut data = profile_info.try_borrow_mut_data()?;
+            profile.try_serialize(&mut &mut data[..])?;
+        }
+
+        ctx.accounts.migration.close(ctx.accounts.authority.to_account_info())
+    }
+
+    /// Upgrades a todo created with the `LegacyTodo` layout. Legacy todos carry
+    /// no timestamps, so the time of the migration is recorded instead.
+    pub fn migrate_todo(ctx: Context<MigrateTodo>, _index: u64) -> Result<()> {
+        let todo_info = ctx.accounts.todo.to_account_info();
+
+        require_keys_eq!(*todo_info.owner, crate::ID, ErrorCode::AccountOwnedByWrongProgram);
+
+        let legacy = {
+            let data = todo_info.try_borrow_data()?;
+
+            require!(data.len() == 8 + LegacyTodo::SPACE, AppError::TodoAlreadyMigrated);
+            require!(data[..8] == Todo::DISCRIMINATOR, ErrorCode::AccountDiscriminatorMismatch);
+
+            LegacyTodo::deserialize(&mut &data[8..])?
+        };
+
+        require_keys_eq!(legacy.profile, ctx.accounts.profile.key(), AppError::InvalidAuthority);
+
+        grow_account(
+            &todo_info,
+            &ctx.accounts.authority,
+            &ctx.accounts.system_program,
+            8 + Todo::INIT_SPACE,
+        )?;
+
+        let now = Clock::get()?.unix_timestamp;
+
+        let todo = Todo {
+            profile: legacy.profile,
+            content: legacy.content,
+            completed: legacy.completed,
+            due_at: None,
+            priority: Priority::Medium,
+            tags: Vec::new(),
+            created_at: now,
+            updated_at: now,
+            completed_at: legacy.completed.then_some(now),
+        };
+
+        let mut data = todo_info.try_borrow_mut_data()?;
+        todo.try_serialize(&mut &mut data[..])?;
+
         Ok(())
     }
 }
 
+/// Reallocates an account still in a legacy layout to `new_len`, topping up its
+/// rent from `payer` first.
+fn grow_account<'info>(
+    account: &AccountInfo<'info>,
+    payer: &Signer<'info>,
+    system_program: &Program<'info, System>,
+    new_len: usize,
+) -> Result<()> {
+    let new_rent = Rent::get()?.minimum_balance(new_len);
+    let lamports = account.lamports();
+
+    if new_rent > lamports {
+        system_program::transfer(
+            CpiContext::new(
+                system_program.to_account_info(),
+                system_program::Transfer {
+                    from: payer.to_account_info(),
+                    to: account.clone(),
+                },
+            ),
+            new_rent - lamports,
+        )?;
+    }
+
+    account.realloc(new_len, false)?;
+
+    Ok(())
+}
+
 #[derive(Accounts)]
 pub struct CreateProfile<'info> {
     #[account(mut)]
@@ -68,14 +464,191 @@ pub struct CreateProfile<'info> {
     pub system_program: Program<'info, System>,
 }
 
+#[derive(Accounts)]
+pub struct UpdateProfile<'info> {
+    authority: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = profile.is_authority(authority.key()) @ AppError::InvalidAuthority
+    )]
+    profile: Account<'info, Profile>,
+}
+
+#[derive(Accounts)]
+pub struct ProposeAuthority<'info> {
+    #[account(mut)]
+    authority: Signer<'info>,
+
+    /// collaborators were invited by the current authority, the new one starts
+    /// without any
+    #[account(
+        constraint = profile.is_authority(authority.key()) @ AppError::InvalidAuthority,
+        constraint = profile.collaborator_count == 0 @ AppError::ProfileHasCollaborators
+    )]
+    profile: Account<'info, Profile>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + PendingAuthority::INIT_SPACE,
+        seeds = [PENDING_AUTHORITY_SEED, profile.key().as_ref()],
+        bump
+    )]
+    pending_authority: Account<'info, PendingAuthority>,
+
+    system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CancelAuthorityTransfer<'info> {
+    #[account(mut)]
+    authority: Signer<'info>,
+
+    #[account(
+        constraint = profile.is_authority(authority.key()) @ AppError::InvalidAuthority
+    )]
+    profile: Account<'info, Profile>,
+
+    #[account(
+        mut,
+        close = authority,
+        seeds = [PENDING_AUTHORITY_SEED, profile.key().as_ref()],
+        bump,
+        constraint = pending_authority.proposed_by == authority.key() @ AppError::InvalidAuthority
+    )]
+    pending_authority: Account<'info, PendingAuthority>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptAuthority<'info> {
+    new_authority: Signer<'info>,
+
+    /// CHECK: only receives the rent of `pending_authority`, checked against `proposed_by`
+    #[account(mut)]
+    proposed_by: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        constraint = profile.is_authority(proposed_by.key()) @ AppError::InvalidAuthority,
+        constraint = profile.collaborator_count == 0 @ AppError::ProfileHasCollaborators
+    )]
+    profile: Account<'info, Profile>,
+
+    #[account(
+        mut,
+        close = proposed_by,
+        seeds = [PENDING_AUTHORITY_SEED, profile.key().as_ref()],
+        bump,
+        constraint = pending_authority.new_authority == new_authority.key() @ AppError::InvalidAuthority,
+        constraint = pending_authority.proposed_by == proposed_by.key() @ AppError::InvalidAuthority
+    )]
+    pending_authority: Account<'info, PendingAuthority>,
+}
+
+#[derive(Accounts)]
+pub struct CloseProfile<'info> {
+    #[account(mut)]
+    authority: Signer<'info>,
+
+    #[account(
+        mut,
+        close = authority,
+        constraint = profile.is_authority(authority.key()) @ AppError::InvalidAuthority,
+        constraint = profile.todo_count == 0 @ AppError::ProfileHasTodos,
+        // collaborator accounts are seeded by the profile address, which a
+        // profile recreated by the same creator gets back
+        constraint = profile.collaborator_count == 0 @ AppError::ProfileHasCollaborators
+    )]
+    profile: Account<'info, Profile>,
+
+    /// CHECK: must not exist, a pending transfer is cancelled before closing
+    #[account(
+        seeds = [PENDING_AUTHORITY_SEED, profile.key().as_ref()],
+        bump,
+        constraint = pending_authority.data_is_empty() @ AppError::AuthorityTransferPending
+    )]
+    pending_authority: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(member: Pubkey)]
+pub struct InviteCollaborator<'info> {
+    #[account(mut)]
+    inviter: Signer<'info>,
+
+    /// the inviter's own collaborator account, unless they are the authority
+    #[account(
+        seeds = [COLLABORATOR_SEED, profile.key().as_ref(), inviter.key().as_ref()],
+        bump
+    )]
+    inviter_collaborator: Option<Account<'info, Collaborator>>,
+
+    #[account(
+        mut,
+        constraint = profile.can(inviter.key(), inviter_collaborator.as_deref(), Role::Admin) @ AppError::InvalidAuthority
+    )]
+    profile: Account<'info, Profile>,
+
+    #[account(
+        init,
+        payer = inviter,
+        space = 8 + Collaborator::INIT_SPACE,
+        seeds = [COLLABORATOR_SEED, profile.key().as_ref(), member.as_ref()],
+        bump
+    )]
+    collaborator: Account<'info, Collaborator>,
+
+    system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(member: Pubkey)]
+pub struct RevokeCollaborator<'info> {
+    revoker: Signer<'info>,
+
+    /// the revoker's own collaborator account, unless they are the authority
+    #[account(
+        seeds = [COLLABORATOR_SEED, profile.key().as_ref(), revoker.key().as_ref()],
+        bump
+    )]
+    revoker_collaborator: Option<Account<'info, Collaborator>>,
+
+    #[account(
+        mut,
+        constraint = revoker.key() == member
+            || profile.can(revoker.key(), revoker_collaborator.as_deref(), Role::Admin) @ AppError::InvalidAuthority
+    )]
+    profile: Account<'info, Profile>,
+
+    /// CHECK: only receives the rent of `collaborator`, checked against `invited_by`
+    #[account(mut)]
+    invited_by: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        close = invited_by,
+        seeds = [COLLABORATOR_SEED, profile.key().as_ref(), member.as_ref()],
+        bump,
+        constraint = collaborator.invited_by == invited_by.key() @ AppError::InvalidAuthority
+    )]
+    collaborator: Account<'info, Collaborator>,
+}
+
 #[derive(Accounts)]
 pub struct CreateTodo<'info> {
     #[account(mut)]
     creator: Signer<'info>,
 
-    #[account(mut, 
-        // has_one = authority
-        constraint = profile.authority == creator.key() @ AppError::InvalidAuthority
+    /// the creator's collaborator account, unless they are the authority
+    #[account(
+        seeds = [COLLABORATOR_SEED, profile.key().as_ref(), creator.key().as_ref()],
+        bump
+    )]
+    collaborator: Option<Account<'info, Collaborator>>,
+
+    #[account(mut,
+        constraint = profile.can(creator.key(), collaborator.as_deref(), Role::Editor) @ AppError::InvalidAuthority
     )]
     profile: Account<'info, Profile>,
 
@@ -83,13 +656,141 @@ pub struct CreateTodo<'info> {
         init,
         payer = creator,
         space = 8 + Todo::INIT_SPACE,
-        seeds = [TODO_SEED, profile.key().as_ref(), profile.todo_count.to_le_bytes().as_ref()],
+        seeds = [TODO_SEED, profile.key().as_ref(), Todo::index_seed(profile.next_todo_index).as_ref()],
+        bump
+    )]
+    todo: Account<'info, Todo>,
+
+    system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(index: u64)]
+pub struct ToggleTodo<'info> {
+    authority: Signer<'info>,
+
+    /// the signer's collaborator account, unless they are the authority
+    #[account(
+        seeds = [COLLABORATOR_SEED, profile.key().as_ref(), authority.key().as_ref()],
+        bump
+    )]
+    collaborator: Option<Account<'info, Collaborator>>,
+
+    #[account(
+        constraint = profile.can(authority.key(), collaborator.as_deref(), Role::Editor) @ AppError::InvalidAuthority
+    )]
+    profile: Account<'info, Profile>,
+
+    #[account(
+        mut,
+        seeds = [TODO_SEED, profile.key().as_ref(), Todo::index_seed(index).as_ref()],
+        bump,
+        constraint = todo.profile == profile.key() @ AppError::InvalidAuthority
+    )]
+    todo: Account<'info, Todo>,
+}
+
+/// Editors may update todos, like they create and toggle them.
+#[derive(Accounts)]
+#[instruction(index: u64)]
+pub struct UpdateTodo<'info> {
+    authority: Signer<'info>,
+
+    /// the signer's collaborator account, unless they are the authority
+    #[account(
+        seeds = [COLLABORATOR_SEED, profile.key().as_ref(), authority.key().as_ref()],
+        bump
+    )]
+    collaborator: Option<Account<'info, Collaborator>>,
+
+    #[account(
+        constraint = profile.can(authority.key(), collaborator.as_deref(), Role::Editor) @ AppError::InvalidAuthority
+    )]
+    profile: Account<'info, Profile>,
+
+    #[account(
+        mut,
+        seeds = [TODO_SEED, profile.key().as_ref(), Todo::index_seed(index).as_ref()],
+        bump,
+        constraint = todo.profile == profile.key() @ AppError::InvalidAuthority
+    )]
+    todo: Account<'info, Todo>,
+}
+
+/// Deleting takes an admin, as it drops the todo for every collaborator.
+#[derive(Accounts)]
+#[instruction(index: u64)]
+pub struct DeleteTodo<'info> {
+    #[account(mut)]
+    authority: Signer<'info>,
+
+    /// the signer's collaborator account, unless they are the authority
+    #[account(
+        seeds = [COLLABORATOR_SEED, profile.key().as_ref(), authority.key().as_ref()],
         bump
     )]
+    collaborator: Option<Account<'info, Collaborator>>,
+
+    #[account(
+        mut,
+        constraint = profile.can(authority.key(), collaborator.as_deref(), Role::Admin) @ AppError::InvalidAuthority
+    )]
+    profile: Account<'info, Profile>,
+
+    #[account(
+        mut,
+        close = authority,
+        seeds = [TODO_SEED, profile.key().as_ref(), Todo::index_seed(index).as_ref()],
+        bump,
+        constraint = todo.profile == profile.key() @ AppError::InvalidAuthority
+    )]
     todo: Account<'info, Todo>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateProfile<'info> {
+    #[account(mut)]
+    authority: Signer<'info>,
+
+    /// CHECK: still in the legacy layout, decoded by hand in `migrate_profile`
+    #[account(
+        mut,
+        seeds = [PROFILE_SEED, authority.key().as_ref()],
+        bump
+    )]
+    profile: UncheckedAccount<'info>,
+
+    /// created by the first batch, closed by the last one
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + ProfileMigration::INIT_SPACE,
+        seeds = [PROFILE_MIGRATION_SEED, profile.key().as_ref()],
+        bump
+    )]
+    migration: Account<'info, ProfileMigration>,
 
     system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-pub struct ToggleTodo {}
+#[instruction(index: u64)]
+pub struct MigrateTodo<'info> {
+    #[account(mut)]
+    authority: Signer<'info>,
+
+    #[account(
+        constraint = profile.is_authority(authority.key()) @ AppError::InvalidAuthority
+    )]
+    profile: Account<'info, Profile>,
+
+    /// CHECK: still in the legacy layout, decoded by hand in `migrate_todo`
+    #[account(
+        mut,
+        seeds = [TODO_SEED, profile.key().as_ref(), Todo::index_seed(index).as_ref()],
+        bump
+    )]
+    todo: UncheckedAccount<'info>,
+
+    system_program: Program<'info, System>,
+}
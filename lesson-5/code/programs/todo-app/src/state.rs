@@ -1,5 +1,10 @@
 use anchor_lang::prelude::*;
 
+use crate::error::AppError;
+
+/// A profile stays at the address seeded by its creator, also once its
+/// authority was transferred, so clients look it up by `authority` rather than
+/// deriving it from the signer.
 #[account]
 pub struct Profile {
     pub key: Pubkey,
@@ -8,16 +13,113 @@ pub struct Profile {
 
     pub authority: Pubkey,
 
-    pub todo_count: u8,
+    pub todo_count: u64, // live todos
+
+    pub next_todo_index: u64, // seed of the next todo, never decremented
+
+    pub collaborator_count: u64, // live collaborator accounts
 }
 
 impl Profile {
+    pub const SPACE: usize = 32 + // key
+                            (4 + 100) // name
+                            + 32 // authority
+                            + 8 // todo_count
+                            + 8 // next_todo_index
+                            + 8; // collaborator_count
+
+    /// Whether `member` is the authority of this profile, the only one allowed
+    /// to manage the profile itself: rename, transfer, close or migrate it.
+    pub fn is_authority(&self, member: Pubkey) -> bool {
+        self.authority == member
+    }
+
+    /// Whether `member` may act on this profile with at least `role`. The
+    /// authority may do anything, anyone else needs a `Collaborator` account of
+    /// this profile granting them a high enough role.
+    pub fn can(&self, member: Pubkey, collaborator: Option<&Collaborator>, role: Role) -> bool {
+        if self.is_authority(member) {
+            return true;
+        }
+
+        collaborator.is_some_and(|collaborator| {
+            collaborator.profile == self.key && collaborator.member == member && collaborator.role >= role
+        })
+    }
+}
+
+/// `Profile` layout before the todo counters were widened, kept around so
+/// `migrate_profile` can decode accounts that have not been upgraded yet.
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct LegacyProfile {
+    pub key: Pubkey,
+
+    pub name: String,
+
+    pub authority: Pubkey,
+
+    pub todo_count: u8,
+}
+
+impl LegacyProfile {
     pub const SPACE: usize = 32 + // key
                             (4 + 100) // name
                             + 32 // authority
                             + 1; // todo_count
 }
 
+/// Progress of a `migrate_profile` run, which takes the todos of a legacy
+/// profile in batches as there can be more than fit in a transaction. Closed
+/// with the last batch.
+#[account]
+#[derive(InitSpace)]
+pub struct ProfileMigration {
+    pub profile: Pubkey,
+
+    pub next_index: u64, // first todo index of the next batch
+
+    pub todo_count: u64, // live todos found so far
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct PendingAuthority {
+    pub profile: Pubkey,
+
+    pub proposed_by: Pubkey,
+
+    pub new_authority: Pubkey,
+}
+
+/// Roles are ordered, each one granting everything the previous one does.
+/// Viewers gain nothing on chain, where accounts are public anyway, but let
+/// clients list the boards shared with a wallet.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, InitSpace)]
+pub enum Role {
+    Viewer,
+    Editor, // create, toggle and update todos
+    Admin,  // delete todos, invite and revoke collaborators
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Collaborator {
+    pub profile: Pubkey,
+
+    pub member: Pubkey,
+
+    pub role: Role,
+
+    pub invited_by: Pubkey, // receives the rent back on revoke
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub enum Priority {
+    Low,
+    Medium,
+    High,
+}
+
 #[account]
 #[derive(InitSpace)]
 pub struct Todo {
@@ -27,4 +129,74 @@ pub struct Todo {
     pub content: String,
 
     pub completed: bool,
+
+    pub due_at: Option<i64>,
+
+    pub priority: Priority,
+
+    #[max_len(5, 32)]
+    pub tags: Vec<String>,
+
+    pub created_at: i64,
+
+    pub updated_at: i64,
+
+    pub completed_at: Option<i64>,
+}
+
+impl Todo {
+    pub const MAX_TAGS: usize = 5;
+
+    pub const MAX_TAG_LEN: usize = 32;
+
+    /// Minimal little-endian encoding of `index`, so todos below 256 keep the
+    /// single byte seed they were created with under the `u8` counter.
+    pub fn index_seed(index: u64) -> Vec<u8> {
+        let bytes = index.to_le_bytes();
+        let len = bytes.iter().rposition(|b| *b != 0).map_or(1, |i| i + 1);
+
+        bytes[..len].to_vec()
+    }
+
+    pub fn validate_content(content: &str) -> Result<()> {
+        require!(content.len() <= 200, AppError::ContentTooLong);
+
+        Ok(())
+    }
+
+    pub fn validate_due_at(due_at: Option<i64>, now: i64) -> Result<()> {
+        if let Some(due_at) = due_at {
+            require!(due_at > now, AppError::DueDateInPast);
+        }
+
+        Ok(())
+    }
+
+    pub fn validate_tags(tags: &[String]) -> Result<()> {
+        require!(tags.len() <= Self::MAX_TAGS, AppError::TooManyTags);
+
+        for tag in tags {
+            require!(!tag.is_empty() && tag.len() <= Self::MAX_TAG_LEN, AppError::InvalidTag);
+        }
+
+        Ok(())
+    }
+}
+
+/// `Todo` layout before due dates, priorities, tags and timestamps were added.
+/// It is also a prefix of the current layout, which `migrate_profile` relies on
+/// to read the owning profile of a todo in either layout.
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct LegacyTodo {
+    pub profile: Pubkey,
+
+    pub content: String,
+
+    pub completed: bool,
+}
+
+impl LegacyTodo {
+    pub const SPACE: usize = 32 + // profile
+                            (4 + 200) // content
+                            + 1; // completed
 }
@@ -0,0 +1,904 @@
+use anchor_lang::{
+    solana_program::pubkey::Pubkey,
+    AccountSerialize, AnchorSerialize, Discriminator, InstructionData, Space, ToAccountMetas,
+};
+use solana_program_test::{BanksClientError, ProgramTest, ProgramTestContext};
+use solana_sdk::{
+    account::Account,
+    instruction::{AccountMeta, Instruction, InstructionError},
+    message::Message,
+    packet::PACKET_DATA_SIZE,
+    rent::Rent,
+    signature::{Keypair, Signer},
+    system_instruction, system_program,
+    transaction::{Transaction, TransactionError},
+};
+use todo_app::{
+    constant::{PENDING_AUTHORITY_SEED, PROFILE_MIGRATION_SEED},
+    error::AppError,
+    state::{LegacyProfile, LegacyTodo, Priority, Profile, Todo},
+};
+use todo_app_client::{
+    collaborator_address, parse_events, profile_address, todo_address, Collaborator, Role, TodoAppEvent,
+};
+
+const NAME: &str = "Khac Vy";
+const CONTENT: &str = "Do Solana bootcamp homework";
+
+// Runs the program as built by `anchor build`, so it executes like on a
+// validator and its events reach the transaction logs. `cargo test-sbf` builds
+// it first; plain `cargo test` needs `SBF_OUT_DIR=target/deploy`.
+fn program_test() -> ProgramTest {
+    let mut program_test = ProgramTest::new("todo_app", todo_app::ID, None);
+    program_test.prefer_bpf(true);
+
+    program_test
+}
+
+/// Sends `instructions` paid by the context payer.
+async fn process(
+    context: &mut ProgramTestContext,
+    instructions: &[Instruction],
+    signers: &[&Keypair],
+) -> Result<(), BanksClientError> {
+    let blockhash = context.banks_client.get_latest_blockhash().await?;
+
+    let mut all_signers = vec![&context.payer];
+    all_signers.extend_from_slice(signers);
+
+    let transaction = Transaction::new_signed_with_payer(
+        instructions,
+        Some(&context.payer.pubkey()),
+        &all_signers,
+        blockhash,
+    );
+
+    context.banks_client.process_transaction(transaction).await
+}
+
+fn assert_app_error<T: std::fmt::Debug>(result: Result<T, BanksClientError>, error: AppError) {
+    match result {
+        Err(BanksClientError::TransactionError(TransactionError::InstructionError(
+            _,
+            InstructionError::Custom(code),
+        ))) => assert_eq!(code, u32::from(error), "unexpected error code"),
+        other => panic!("expected {error:?}, got {other:?}"),
+    }
+}
+
+async fn fetch<T: anchor_lang::AccountDeserialize>(context: &mut ProgramTestContext, address: &Pubkey) -> T {
+    let account = context
+        .banks_client
+        .get_account(*address)
+        .await
+        .unwrap()
+        .expect("account not found");
+
+    T::try_deserialize(&mut account.data.as_slice()).unwrap()
+}
+
+async fn fund(context: &mut ProgramTestContext, to: &Pubkey) {
+    let transfer = system_instruction::transfer(&context.payer.pubkey(), to, 1_000_000_000);
+
+    process(context, &[transfer], &[]).await.unwrap();
+}
+
+/// Creates the profile of the context payer and returns its address.
+async fn create_profile(context: &mut ProgramTestContext) -> Pubkey {
+    let creator = context.payer.pubkey();
+
+    process(context, &[todo_app_client::create_profile(&creator, NAME)], &[])
+        .await
+        .unwrap();
+
+    profile_address(&creator).0
+}
+
+/// Creates a todo on the payer's profile and returns its index and address.
+async fn create_todo(context: &mut ProgramTestContext, profile: &Pubkey) -> (u64, Pubkey) {
+    let creator = context.payer.pubkey();
+    let index = fetch::<Profile>(context, profile).await.next_todo_index;
+
+    let instruction = todo_app_client::create_todo(&creator, false, profile, index, CONTENT, None, Priority::Medium, vec![]);
+    process(context, &[instruction], &[]).await.unwrap();
+
+    (index, todo_address(profile, index).0)
+}
+
+fn instruction(accounts: impl ToAccountMetas, data: impl InstructionData) -> Instruction {
+    Instruction {
+        program_id: todo_app::ID,
+        accounts: accounts.to_account_metas(None),
+        data: data.data(),
+    }
+}
+
+fn update_todo(
+    authority: &Pubkey,
+    as_collaborator: bool,
+    profile: &Pubkey,
+    index: u64,
+    content: &str,
+    tags: Option<Vec<String>>,
+) -> Instruction {
+    instruction(
+        todo_app::accounts::UpdateTodo {
+            authority: *authority,
+            collaborator: as_collaborator.then(|| collaborator_address(profile, authority).0),
+            profile: *profile,
+            todo: todo_address(profile, index).0,
+        },
+        todo_app::instruction::UpdateTodo {
+            index,
+            content: Some(content.to_string()),
+            priority: None,
+            tags,
+        },
+    )
+}
+
+fn delete_todo(authority: &Pubkey, as_collaborator: bool, profile: &Pubkey, index: u64) -> Instruction {
+    instruction(
+        todo_app::accounts::DeleteTodo {
+            authority: *authority,
+            collaborator: as_collaborator.then(|| collaborator_address(profile, authority).0),
+            profile: *profile,
+            todo: todo_address(profile, index).0,
+        },
+        todo_app::instruction::DeleteTodo { index },
+    )
+}
+
+fn close_profile(authority: &Pubkey, profile: &Pubkey) -> Instruction {
+    instruction(
+        todo_app::accounts::CloseProfile {
+            authority: *authority,
+            profile: *profile,
+            pending_authority: pending_authority_address(profile),
+        },
+        todo_app::instruction::CloseProfile {},
+    )
+}
+
+fn propose_authority(authority: &Pubkey, profile: &Pubkey, new_authority: &Pubkey) -> Instruction {
+    instruction(
+        todo_app::accounts::ProposeAuthority {
+            authority: *authority,
+            profile: *profile,
+            pending_authority: pending_authority_address(profile),
+            system_program: system_program::ID,
+        },
+        todo_app::instruction::ProposeAuthority {
+            new_authority: *new_authority,
+        },
+    )
+}
+
+fn cancel_authority_transfer(authority: &Pubkey, profile: &Pubkey) -> Instruction {
+    instruction(
+        todo_app::accounts::CancelAuthorityTransfer {
+            authority: *authority,
+            profile: *profile,
+            pending_authority: pending_authority_address(profile),
+        },
+        todo_app::instruction::CancelAuthorityTransfer {},
+    )
+}
+
+fn pending_authority_address(profile: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[PENDING_AUTHORITY_SEED, profile.as_ref()], &todo_app::ID).0
+}
+
+/// `migrate_profile` passing the todo accounts of `todos`, which are the
+/// indexes from `start` on when the call is legit.
+fn migrate_profile(authority: &Pubkey, start: u64, todos: &[u64]) -> Instruction {
+    let profile = profile_address(authority).0;
+
+    let mut migrate = instruction(
+        todo_app::accounts::MigrateProfile {
+            authority: *authority,
+            profile,
+            migration: Pubkey::find_program_address(&[PROFILE_MIGRATION_SEED, profile.as_ref()], &todo_app::ID).0,
+            system_program: system_program::ID,
+        },
+        todo_app::instruction::MigrateProfile { start },
+    );
+
+    migrate
+        .accounts
+        .extend(todos.iter().map(|index| AccountMeta::new_readonly(todo_address(&profile, *index).0, false)));
+
+    migrate
+}
+
+fn migrate_todo(authority: &Pubkey, profile: &Pubkey, index: u64) -> Instruction {
+    instruction(
+        todo_app::accounts::MigrateTodo {
+            authority: *authority,
+            profile: *profile,
+            todo: todo_address(profile, index).0,
+            system_program: system_program::ID,
+        },
+        todo_app::instruction::MigrateTodo { _index: index },
+    )
+}
+
+/// Builds a program-owned account holding `value` behind `discriminator`,
+/// zero padded to `space` like an account created with `init`.
+fn program_account(discriminator: [u8; 8], value: impl AnchorSerialize, space: usize) -> Account {
+    let mut data = discriminator.to_vec();
+    value.serialize(&mut data).unwrap();
+    data.resize(8 + space, 0);
+
+    Account {
+        lamports: Rent::default().minimum_balance(data.len()),
+        data,
+        owner: todo_app::ID,
+        ..Account::default()
+    }
+}
+
+#[tokio::test]
+async fn create_profile_successfully() {
+    let mut context = program_test().start_with_context().await;
+    let creator = context.payer.pubkey();
+
+    process(&mut context, &[todo_app_client::create_profile(&creator, NAME)], &[])
+        .await
+        .unwrap();
+
+    let profile = profile_address(&creator).0;
+    let profile_account: Profile = fetch(&mut context, &profile).await;
+
+    assert_eq!(profile_account.key, profile);
+    assert_eq!(profile_account.name, NAME);
+    assert_eq!(profile_account.authority, creator);
+    assert_eq!(profile_account.todo_count, 0);
+    assert_eq!(profile_account.next_todo_index, 0);
+}
+
+#[tokio::test]
+async fn create_profile_fails_with_name_too_long() {
+    let mut context = program_test().start_with_context().await;
+    let creator = context.payer.pubkey();
+
+    let result = process(&mut context, &[todo_app_client::create_profile(&creator, &"a".repeat(101))], &[]).await;
+
+    assert_app_error(result, AppError::NameTooLong);
+}
+
+#[tokio::test]
+async fn create_todo_successfully() {
+    let mut context = program_test().start_with_context().await;
+    let creator = context.payer.pubkey();
+    let profile = create_profile(&mut context).await;
+
+    let tags = vec!["bootcamp".to_string()];
+    let instruction = todo_app_client::create_todo(&creator, false, &profile, 0, CONTENT, None, Priority::High, tags.clone());
+    process(&mut context, &[instruction], &[]).await.unwrap();
+
+    let todo = todo_address(&profile, 0).0;
+    let todo_account: Todo = fetch(&mut context, &todo).await;
+
+    assert_eq!(todo_account.profile, profile);
+    assert_eq!(todo_account.content, CONTENT);
+    assert!(!todo_account.completed);
+    assert_eq!(todo_account.priority, Priority::High);
+    assert_eq!(todo_account.tags, tags);
+    assert_eq!(todo_account.created_at, todo_account.updated_at);
+    assert_eq!(todo_account.completed_at, None);
+
+    let profile_account: Profile = fetch(&mut context, &profile).await;
+    assert_eq!(profile_account.todo_count, 1);
+    assert_eq!(profile_account.next_todo_index, 1);
+}
+
+#[tokio::test]
+async fn create_todo_emits_event() {
+    let mut context = program_test().start_with_context().await;
+    let creator = context.payer.pubkey();
+    let profile = create_profile(&mut context).await;
+
+    let tags = vec!["bootcamp".to_string()];
+    let instruction = todo_app_client::create_todo(&creator, false, &profile, 0, CONTENT, None, Priority::High, tags.clone());
+    let blockhash = context.banks_client.get_latest_blockhash().await.unwrap();
+    let transaction = Transaction::new_signed_with_payer(&[instruction], Some(&creator), &[&context.payer], blockhash);
+
+    let processed = context
+        .banks_client
+        .process_transaction_with_metadata(transaction)
+        .await
+        .unwrap();
+    processed.result.unwrap();
+    let logs = processed.metadata.expect("transaction metadata").log_messages;
+
+    match parse_events(&logs).as_slice() {
+        [TodoAppEvent::TodoCreated(event)] => {
+            assert_eq!(event.profile, profile);
+            assert_eq!(event.todo, todo_address(&profile, 0).0);
+            assert_eq!(event.index, 0);
+            assert_eq!(event.content, CONTENT);
+            assert_eq!(event.priority, Priority::High);
+            assert_eq!(event.tags, tags);
+        }
+        events => panic!("unexpected events {events:?}"),
+    }
+}
+
+#[tokio::test]
+async fn create_todo_fails_with_content_too_long() {
+    let mut context = program_test().start_with_context().await;
+    let creator = context.payer.pubkey();
+    let profile = create_profile(&mut context).await;
+
+    let instruction = todo_app_client::create_todo(&creator, false, &profile, 0, &"a".repeat(201), None, Priority::Low, vec![]);
+    let result = process(&mut context, &[instruction], &[]).await;
+
+    assert_app_error(result, AppError::ContentTooLong);
+}
+
+#[tokio::test]
+async fn create_todo_fails_with_invalid_authority() {
+    let mut context = program_test().start_with_context().await;
+    let profile = create_profile(&mut context).await;
+
+    let another_creator = Keypair::new();
+    fund(&mut context, &another_creator.pubkey()).await;
+
+    let instruction =
+        todo_app_client::create_todo(&another_creator.pubkey(), false, &profile, 0, CONTENT, None, Priority::Low, vec![]);
+    let result = process(&mut context, &[instruction], &[&another_creator]).await;
+
+    assert_app_error(result, AppError::InvalidAuthority);
+}
+
+#[tokio::test]
+async fn create_todo_fails_with_due_date_in_past() {
+    let mut context = program_test().start_with_context().await;
+    let creator = context.payer.pubkey();
+    let profile = create_profile(&mut context).await;
+
+    let instruction = todo_app_client::create_todo(&creator, false, &profile, 0, CONTENT, Some(1), Priority::Low, vec![]);
+    let result = process(&mut context, &[instruction], &[]).await;
+
+    assert_app_error(result, AppError::DueDateInPast);
+}
+
+#[tokio::test]
+async fn create_todo_fails_with_invalid_tags() {
+    let mut context = program_test().start_with_context().await;
+    let creator = context.payer.pubkey();
+    let profile = create_profile(&mut context).await;
+
+    let too_many = vec!["tag".to_string(); Todo::MAX_TAGS + 1];
+    let instruction = todo_app_client::create_todo(&creator, false, &profile, 0, CONTENT, None, Priority::Low, too_many);
+    assert_app_error(process(&mut context, &[instruction], &[]).await, AppError::TooManyTags);
+
+    let too_long = vec!["a".repeat(Todo::MAX_TAG_LEN + 1)];
+    let instruction = todo_app_client::create_todo(&creator, false, &profile, 0, CONTENT, None, Priority::Low, too_long);
+    assert_app_error(process(&mut context, &[instruction], &[]).await, AppError::InvalidTag);
+}
+
+#[tokio::test]
+async fn create_todo_fails_with_todo_count_overflow() {
+    let mut program_test = program_test();
+    let creator = Keypair::new();
+    let profile = Pubkey::new_unique();
+
+    program_test.add_account(
+        profile,
+        program_account(
+            Profile::DISCRIMINATOR,
+            Profile {
+                key: profile,
+                name: NAME.to_string(),
+                authority: creator.pubkey(),
+                todo_count: 0,
+                next_todo_index: u64::MAX,
+                collaborator_count: 0,
+            },
+            Profile::SPACE,
+        ),
+    );
+
+    let mut context = program_test.start_with_context().await;
+    fund(&mut context, &creator.pubkey()).await;
+
+    let instruction =
+        todo_app_client::create_todo(&creator.pubkey(), false, &profile, u64::MAX, CONTENT, None, Priority::Low, vec![]);
+    let result = process(&mut context, &[instruction], &[&creator]).await;
+
+    assert_app_error(result, AppError::TodoCountOverflow);
+}
+
+#[tokio::test]
+async fn toggle_todo_successfully() {
+    let mut context = program_test().start_with_context().await;
+    let authority = context.payer.pubkey();
+    let profile = create_profile(&mut context).await;
+    let (index, todo) = create_todo(&mut context, &profile).await;
+
+    process(&mut context, &[todo_app_client::toggle_todo(&authority, false, &profile, index)], &[])
+        .await
+        .unwrap();
+
+    let todo_account: Todo = fetch(&mut context, &todo).await;
+    assert!(todo_account.completed);
+    assert!(todo_account.completed_at.is_some());
+}
+
+#[tokio::test]
+async fn toggle_todo_fails_with_invalid_authority() {
+    let mut context = program_test().start_with_context().await;
+    let profile = create_profile(&mut context).await;
+    let (index, _) = create_todo(&mut context, &profile).await;
+
+    let another_authority = Keypair::new();
+    let instruction = todo_app_client::toggle_todo(&another_authority.pubkey(), false, &profile, index);
+    let result = process(&mut context, &[instruction], &[&another_authority]).await;
+
+    assert_app_error(result, AppError::InvalidAuthority);
+}
+
+/// Invites a funded `member` with `role` on the payer's profile.
+async fn invite(context: &mut ProgramTestContext, profile: &Pubkey, role: Role) -> Keypair {
+    let authority = context.payer.pubkey();
+    let member = Keypair::new();
+    fund(context, &member.pubkey()).await;
+
+    let instruction = todo_app_client::invite_collaborator(&authority, false, profile, &member.pubkey(), role);
+    process(context, &[instruction], &[]).await.unwrap();
+
+    member
+}
+
+#[tokio::test]
+async fn editor_creates_and_toggles_todo() {
+    let mut context = program_test().start_with_context().await;
+    let profile = create_profile(&mut context).await;
+    let editor = invite(&mut context, &profile, Role::Editor).await;
+
+    let collaborator: Collaborator = fetch(&mut context, &collaborator_address(&profile, &editor.pubkey()).0).await;
+    assert_eq!(collaborator.member, editor.pubkey());
+    assert_eq!(collaborator.role, Role::Editor);
+    assert_eq!(collaborator.invited_by, context.payer.pubkey());
+
+    let instructions = [
+        todo_app_client::create_todo(&editor.pubkey(), true, &profile, 0, CONTENT, None, Priority::Low, vec![]),
+        todo_app_client::toggle_todo(&editor.pubkey(), true, &profile, 0),
+    ];
+    process(&mut context, &instructions, &[&editor]).await.unwrap();
+
+    let todo_account: Todo = fetch(&mut context, &todo_address(&profile, 0).0).await;
+    assert!(todo_account.completed);
+}
+
+#[tokio::test]
+async fn viewer_cannot_create_todo() {
+    let mut context = program_test().start_with_context().await;
+    let profile = create_profile(&mut context).await;
+    let viewer = invite(&mut context, &profile, Role::Viewer).await;
+
+    let instruction =
+        todo_app_client::create_todo(&viewer.pubkey(), true, &profile, 0, CONTENT, None, Priority::Low, vec![]);
+    let result = process(&mut context, &[instruction], &[&viewer]).await;
+
+    assert_app_error(result, AppError::InvalidAuthority);
+}
+
+#[tokio::test]
+async fn only_admins_invite_collaborators() {
+    let mut context = program_test().start_with_context().await;
+    let profile = create_profile(&mut context).await;
+    let editor = invite(&mut context, &profile, Role::Editor).await;
+    let admin = invite(&mut context, &profile, Role::Admin).await;
+
+    let member = Pubkey::new_unique();
+
+    let instruction = todo_app_client::invite_collaborator(&editor.pubkey(), true, &profile, &member, Role::Viewer);
+    let result = process(&mut context, &[instruction], &[&editor]).await;
+    assert_app_error(result, AppError::InvalidAuthority);
+
+    let instruction = todo_app_client::invite_collaborator(&admin.pubkey(), true, &profile, &member, Role::Viewer);
+    process(&mut context, &[instruction], &[&admin]).await.unwrap();
+
+    let authority = context.payer.pubkey();
+    let instruction = todo_app_client::invite_collaborator(&admin.pubkey(), true, &profile, &authority, Role::Viewer);
+    let result = process(&mut context, &[instruction], &[&admin]).await;
+    assert_app_error(result, AppError::AuthorityAsCollaborator);
+}
+
+#[tokio::test]
+async fn revoked_editor_loses_access() {
+    let mut context = program_test().start_with_context().await;
+    let authority = context.payer.pubkey();
+    let profile = create_profile(&mut context).await;
+    let (index, _) = create_todo(&mut context, &profile).await;
+    let editor = invite(&mut context, &profile, Role::Editor).await;
+    let (collaborator, _) = collaborator_address(&profile, &editor.pubkey());
+
+    // editors cannot revoke anyone but themselves
+    let another_editor = invite(&mut context, &profile, Role::Editor).await;
+    let instruction =
+        todo_app_client::revoke_collaborator(&editor.pubkey(), true, &profile, &another_editor.pubkey(), &authority);
+    let result = process(&mut context, &[instruction], &[&editor]).await;
+    assert_app_error(result, AppError::InvalidAuthority);
+
+    let instruction = todo_app_client::revoke_collaborator(&authority, false, &profile, &editor.pubkey(), &authority);
+    process(&mut context, &[instruction], &[]).await.unwrap();
+
+    let account = context.banks_client.get_account(collaborator).await.unwrap();
+    assert!(account.is_none());
+
+    // without its collaborator account the editor can only sign as a stranger
+    let instruction = todo_app_client::toggle_todo(&editor.pubkey(), false, &profile, index);
+    let result = process(&mut context, &[instruction], &[&editor]).await;
+    assert_app_error(result, AppError::InvalidAuthority);
+
+    // members may leave on their own
+    let instruction = todo_app_client::revoke_collaborator(
+        &another_editor.pubkey(),
+        true,
+        &profile,
+        &another_editor.pubkey(),
+        &authority,
+    );
+    process(&mut context, &[instruction], &[&another_editor]).await.unwrap();
+}
+
+#[tokio::test]
+async fn editor_updates_but_only_admin_deletes_todo() {
+    let mut context = program_test().start_with_context().await;
+    let profile = create_profile(&mut context).await;
+    let (index, todo) = create_todo(&mut context, &profile).await;
+    let viewer = invite(&mut context, &profile, Role::Viewer).await;
+    let editor = invite(&mut context, &profile, Role::Editor).await;
+    let admin = invite(&mut context, &profile, Role::Admin).await;
+
+    let instruction = update_todo(&viewer.pubkey(), true, &profile, index, "Updated by a viewer", None);
+    let result = process(&mut context, &[instruction], &[&viewer]).await;
+    assert_app_error(result, AppError::InvalidAuthority);
+
+    let instruction = update_todo(&editor.pubkey(), true, &profile, index, "Updated by an editor", None);
+    process(&mut context, &[instruction], &[&editor]).await.unwrap();
+
+    let todo_account: Todo = fetch(&mut context, &todo).await;
+    assert_eq!(todo_account.content, "Updated by an editor");
+
+    let result = process(&mut context, &[delete_todo(&editor.pubkey(), true, &profile, index)], &[&editor]).await;
+    assert_app_error(result, AppError::InvalidAuthority);
+
+    process(&mut context, &[delete_todo(&admin.pubkey(), true, &profile, index)], &[&admin])
+        .await
+        .unwrap();
+
+    assert!(context.banks_client.get_account(todo).await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn recreated_profile_drops_collaborators() {
+    let mut context = program_test().start_with_context().await;
+    let authority = context.payer.pubkey();
+    let profile = create_profile(&mut context).await;
+    let editor = invite(&mut context, &profile, Role::Editor).await;
+
+    let profile_account: Profile = fetch(&mut context, &profile).await;
+    assert_eq!(profile_account.collaborator_count, 1);
+
+    // the editor's account would outlive the profile and apply to the next one
+    // at the same address, and the new authority never invited the editor
+    let result = process(&mut context, &[close_profile(&authority, &profile)], &[]).await;
+    assert_app_error(result, AppError::ProfileHasCollaborators);
+
+    let instruction = propose_authority(&authority, &profile, &Pubkey::new_unique());
+    let result = process(&mut context, &[instruction], &[]).await;
+    assert_app_error(result, AppError::ProfileHasCollaborators);
+
+    let revoke = todo_app_client::revoke_collaborator(&authority, false, &profile, &editor.pubkey(), &authority);
+    process(&mut context, &[revoke, close_profile(&authority, &profile)], &[])
+        .await
+        .unwrap();
+
+    context.get_new_latest_blockhash().await.unwrap();
+    assert_eq!(create_profile(&mut context).await, profile);
+
+    let instruction =
+        todo_app_client::create_todo(&editor.pubkey(), true, &profile, 0, CONTENT, None, Priority::Low, vec![]);
+    match process(&mut context, &[instruction], &[&editor]).await {
+        Err(BanksClientError::TransactionError(TransactionError::InstructionError(
+            _,
+            InstructionError::Custom(code),
+        ))) => assert_eq!(code, u32::from(anchor_lang::error::ErrorCode::AccountNotInitialized)),
+        other => panic!("expected the stale collaborator to be rejected, got {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn update_todo_fails_with_content_too_long() {
+    let mut context = program_test().start_with_context().await;
+    let authority = context.payer.pubkey();
+    let profile = create_profile(&mut context).await;
+    let (index, _) = create_todo(&mut context, &profile).await;
+
+    let instruction = update_todo(&authority, false, &profile, index, &"a".repeat(201), None);
+    let result = process(&mut context, &[instruction], &[]).await;
+
+    assert_app_error(result, AppError::ContentTooLong);
+}
+
+#[tokio::test]
+async fn delete_todo_keeps_next_index() {
+    let mut context = program_test().start_with_context().await;
+    let authority = context.payer.pubkey();
+    let profile = create_profile(&mut context).await;
+    let (index, todo) = create_todo(&mut context, &profile).await;
+
+    process(&mut context, &[delete_todo(&authority, false, &profile, index)], &[])
+        .await
+        .unwrap();
+
+    assert!(context.banks_client.get_account(todo).await.unwrap().is_none());
+
+    let profile_account: Profile = fetch(&mut context, &profile).await;
+    assert_eq!(profile_account.todo_count, 0);
+    assert_eq!(profile_account.next_todo_index, 1);
+}
+
+#[tokio::test]
+async fn delete_todo_fails_with_todo_count_underflow() {
+    let mut program_test = program_test();
+    let authority = Keypair::new();
+    let profile = Pubkey::new_unique();
+
+    // a todo the counter lost track of
+    program_test.add_account(
+        profile,
+        program_account(
+            Profile::DISCRIMINATOR,
+            Profile {
+                key: profile,
+                name: NAME.to_string(),
+                authority: authority.pubkey(),
+                todo_count: 0,
+                next_todo_index: 1,
+                collaborator_count: 0,
+            },
+            Profile::SPACE,
+        ),
+    );
+    program_test.add_account(
+        todo_address(&profile, 0).0,
+        program_account(
+            Todo::DISCRIMINATOR,
+            Todo {
+                profile,
+                content: CONTENT.to_string(),
+                completed: false,
+                due_at: None,
+                priority: Priority::Medium,
+                tags: vec![],
+                created_at: 0,
+                updated_at: 0,
+                completed_at: None,
+            },
+            Todo::INIT_SPACE,
+        ),
+    );
+
+    let mut context = program_test.start_with_context().await;
+    fund(&mut context, &authority.pubkey()).await;
+
+    let result = process(&mut context, &[delete_todo(&authority.pubkey(), false, &profile, 0)], &[&authority]).await;
+
+    assert_app_error(result, AppError::TodoCountUnderflow);
+}
+
+#[tokio::test]
+async fn close_profile_fails_with_live_todos() {
+    let mut context = program_test().start_with_context().await;
+    let authority = context.payer.pubkey();
+    let profile = create_profile(&mut context).await;
+    let (index, _) = create_todo(&mut context, &profile).await;
+
+    let result = process(&mut context, &[close_profile(&authority, &profile)], &[]).await;
+    assert_app_error(result, AppError::ProfileHasTodos);
+
+    process(
+        &mut context,
+        &[delete_todo(&authority, false, &profile, index), close_profile(&authority, &profile)],
+        &[],
+    )
+    .await
+    .unwrap();
+
+    assert!(context.banks_client.get_account(profile).await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn close_profile_fails_with_pending_transfer() {
+    let mut context = program_test().start_with_context().await;
+    let authority = context.payer.pubkey();
+    let profile = create_profile(&mut context).await;
+
+    let instruction = propose_authority(&authority, &profile, &Pubkey::new_unique());
+    process(&mut context, &[instruction], &[]).await.unwrap();
+
+    let result = process(&mut context, &[close_profile(&authority, &profile)], &[]).await;
+    assert_app_error(result, AppError::AuthorityTransferPending);
+
+    process(
+        &mut context,
+        &[cancel_authority_transfer(&authority, &profile), close_profile(&authority, &profile)],
+        &[],
+    )
+    .await
+    .unwrap();
+
+    assert!(context.banks_client.get_account(profile).await.unwrap().is_none());
+    let pending_authority = pending_authority_address(&profile);
+    assert!(context.banks_client.get_account(pending_authority).await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn migrate_legacy_profile_and_todo() {
+    let mut program_test = program_test();
+    let authority = Keypair::new();
+    let profile = profile_address(&authority.pubkey()).0;
+
+    program_test.add_account(
+        profile,
+        program_account(
+            Profile::DISCRIMINATOR,
+            LegacyProfile {
+                key: profile,
+                name: NAME.to_string(),
+                authority: authority.pubkey(),
+                todo_count: 2,
+            },
+            LegacyProfile::SPACE,
+        ),
+    );
+
+    // index 0 was deleted before the migration, only index 1 is still live
+    let legacy_todo = todo_address(&profile, 1).0;
+    program_test.add_account(
+        legacy_todo,
+        program_account(
+            Todo::DISCRIMINATOR,
+            LegacyTodo {
+                profile,
+                content: CONTENT.to_string(),
+                completed: true,
+            },
+            LegacyTodo::SPACE,
+        ),
+    );
+
+    let mut context = program_test.start_with_context().await;
+    fund(&mut context, &authority.pubkey()).await;
+
+    // leaving live todos out, repeating or reordering them can't lower the count
+    for (start, todos) in [(0, &[1][..]), (0, &[1, 1]), (0, &[1, 0]), (0, &[0, 1, 2]), (1, &[1])] {
+        let result = process(&mut context, &[migrate_profile(&authority.pubkey(), start, todos)], &[&authority]).await;
+        assert_app_error(result, AppError::TodoAccountsMismatch);
+    }
+
+    process(&mut context, &[migrate_profile(&authority.pubkey(), 0, &[0, 1])], &[&authority])
+        .await
+        .unwrap();
+
+    let profile_account: Profile = fetch(&mut context, &profile).await;
+    assert_eq!(profile_account.name, NAME);
+    assert_eq!(profile_account.authority, authority.pubkey());
+    assert_eq!(profile_account.todo_count, 1);
+    assert_eq!(profile_account.next_todo_index, 2);
+
+    let result = process(&mut context, &[migrate_profile(&authority.pubkey(), 0, &[])], &[&authority]).await;
+    assert_app_error(result, AppError::ProfileAlreadyMigrated);
+
+    process(&mut context, &[migrate_todo(&authority.pubkey(), &profile, 1)], &[&authority])
+        .await
+        .unwrap();
+
+    let todo_account: Todo = fetch(&mut context, &legacy_todo).await;
+    assert_eq!(todo_account.content, CONTENT);
+    assert!(todo_account.completed);
+    assert_eq!(todo_account.priority, Priority::Medium);
+    assert!(todo_account.completed_at.is_some());
+
+    let result = process(&mut context, &[migrate_todo(&authority.pubkey(), &profile, 1)], &[&authority]).await;
+    assert_app_error(result, AppError::TodoAlreadyMigrated);
+}
+
+#[tokio::test]
+async fn migrate_legacy_profile_in_batches() {
+    const TODO_COUNT: u8 = 60;
+    const BATCH: usize = 25;
+
+    let mut program_test = program_test();
+    let authority = Keypair::new();
+    let profile = profile_address(&authority.pubkey()).0;
+
+    program_test.add_account(
+        profile,
+        program_account(
+            Profile::DISCRIMINATOR,
+            LegacyProfile {
+                key: profile,
+                name: NAME.to_string(),
+                authority: authority.pubkey(),
+                todo_count: TODO_COUNT,
+            },
+            LegacyProfile::SPACE,
+        ),
+    );
+
+    // every third todo is still live
+    for index in (0..u64::from(TODO_COUNT)).step_by(3) {
+        program_test.add_account(
+            todo_address(&profile, index).0,
+            program_account(
+                Todo::DISCRIMINATOR,
+                LegacyTodo {
+                    profile,
+                    content: CONTENT.to_string(),
+                    completed: false,
+                },
+                LegacyTodo::SPACE,
+            ),
+        );
+    }
+
+    let mut context = program_test.start_with_context().await;
+    fund(&mut context, &authority.pubkey()).await;
+
+    let indexes: Vec<u64> = (0..u64::from(TODO_COUNT)).collect();
+
+    let all_at_once = migrate_profile(&authority.pubkey(), 0, &indexes);
+    assert!(Message::new(&[all_at_once], Some(&context.payer.pubkey())).serialize().len() > PACKET_DATA_SIZE);
+
+    let mut batches = indexes.chunks(BATCH).zip((0..).step_by(BATCH));
+
+    let (first, start) = batches.next().unwrap();
+    process(&mut context, &[migrate_profile(&authority.pubkey(), start, first)], &[&authority])
+        .await
+        .unwrap();
+
+    // the profile keeps its legacy layout until the last batch
+    let account = context.banks_client.get_account(profile).await.unwrap().unwrap();
+    assert_eq!(account.data.len(), 8 + LegacyProfile::SPACE);
+
+    // batches pick up where the previous one stopped
+    let result = process(&mut context, &[migrate_profile(&authority.pubkey(), 0, first)], &[&authority]).await;
+    assert_app_error(result, AppError::TodoAccountsMismatch);
+
+    for (todos, start) in batches {
+        process(&mut context, &[migrate_profile(&authority.pubkey(), start, todos)], &[&authority])
+            .await
+            .unwrap();
+    }
+
+    let profile_account: Profile = fetch(&mut context, &profile).await;
+    assert_eq!(profile_account.todo_count, 20);
+    assert_eq!(profile_account.next_todo_index, u64::from(TODO_COUNT));
+
+    let migration = Pubkey::find_program_address(&[PROFILE_MIGRATION_SEED, profile.as_ref()], &todo_app::ID).0;
+    assert!(context.banks_client.get_account(migration).await.unwrap().is_none());
+}
+
+#[test]
+fn profile_serializes_within_space() {
+    let profile = Profile {
+        key: Pubkey::new_unique(),
+        name: "a".repeat(100),
+        authority: Pubkey::new_unique(),
+        todo_count: u64::MAX,
+        next_todo_index: u64::MAX,
+        collaborator_count: u64::MAX,
+    };
+
+    let mut data = Vec::new();
+    profile.try_serialize(&mut data).unwrap();
+
+    assert_eq!(data.len(), 8 + Profile::SPACE);
+}
@@ -0,0 +1,119 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { TodoApp } from "../target/types/todo_app";
+import { assert, expect } from "chai";
+import { todoIndexSeed, withErrorTest } from "./utils";
+
+describe("todo-app", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.TodoApp as Program<TodoApp>;
+  const name = "Khac Vy";
+
+  const content = "Do Solana bootcamp homework";
+
+  let profile: anchor.web3.PublicKey;
+  let pendingAuthority: anchor.web3.PublicKey;
+  let todo: anchor.web3.PublicKey;
+  let todoIndex: number;
+
+  before(async () => {
+    [profile] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("profile"), provider.publicKey.toBytes()],
+      program.programId
+    );
+
+    [pendingAuthority] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("pending_authority"), profile.toBytes()],
+      program.programId
+    );
+
+    const tx = await program.methods
+      .createProfile(name)
+      .accounts({
+        creator: provider.publicKey,
+        profile,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    console.log("Create profile success", tx);
+
+    const profileAccount = await program.account.profile.fetch(profile);
+    todoIndex = profileAccount.nextTodoIndex.toNumber();
+
+    [todo] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("todo"), profile.toBytes(), todoIndexSeed(todoIndex)],
+      program.programId
+    );
+
+    await program.methods
+      .createTodo(content, null, { medium: {} }, [])
+      .accounts({
+        creator: provider.publicKey,
+        collaborator: null,
+        profile,
+        todo,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+  });
+
+  it("Close profile failed while todos exist", async () => {
+    await withErrorTest(async () => {
+      try {
+        const tx = await program.methods
+          .closeProfile()
+          .accounts({
+            authority: provider.publicKey,
+            profile,
+            pendingAuthority,
+          })
+          .rpc();
+
+        console.log("Your transaction signature", tx);
+
+        assert.ok(false);
+      } catch (_err) {
+        // console.log(_err);
+        assert.isTrue(_err instanceof anchor.AnchorError);
+        const err: anchor.AnchorError = _err;
+        assert.strictEqual(err.error.errorMessage, "Profile still has todos");
+        assert.strictEqual(err.error.errorCode.code, "ProfileHasTodos");
+        assert.strictEqual(
+          err.program.toString(),
+          program.programId.toString()
+        );
+      }
+    });
+  });
+
+  it("Close profile successfully", async () => {
+    await program.methods
+      .deleteTodo(new anchor.BN(todoIndex))
+      .accounts({
+        authority: provider.publicKey,
+        collaborator: null,
+        profile,
+        todo,
+      })
+      .rpc();
+
+    const tx = await program.methods
+      .closeProfile()
+      .accounts({
+        authority: provider.publicKey,
+        profile,
+        pendingAuthority,
+      })
+      .rpc();
+
+    console.log("Your transaction signature", tx);
+
+    const profileAccount = await program.account.profile.fetchNullable(
+      profile
+    );
+    expect(profileAccount).to.equal(null);
+  });
+});
@@ -0,0 +1,207 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { TodoApp } from "../target/types/todo_app";
+import { assert, expect } from "chai";
+import { todoIndexSeed, withErrorTest } from "./utils";
+
+describe("todo-app", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.TodoApp as Program<TodoApp>;
+  const name = "Khac Vy";
+
+  const content = "Do Solana bootcamp homework";
+
+  const editor = anchor.web3.Keypair.generate();
+  const viewer = anchor.web3.Keypair.generate();
+
+  let profile: anchor.web3.PublicKey;
+  let editorCollaborator: anchor.web3.PublicKey;
+  let viewerCollaborator: anchor.web3.PublicKey;
+
+  const collaboratorAddress = (member: anchor.web3.PublicKey) =>
+    anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("collaborator"), profile.toBytes(), member.toBytes()],
+      program.programId
+    )[0];
+
+  const nextTodo = async () => {
+    const profileAccount = await program.account.profile.fetch(profile);
+    const index = profileAccount.nextTodoIndex.toNumber();
+
+    const [todo] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("todo"), profile.toBytes(), todoIndexSeed(index)],
+      program.programId
+    );
+
+    return { index, todo };
+  };
+
+  before(async () => {
+    [profile] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("profile"), provider.publicKey.toBytes()],
+      program.programId
+    );
+
+    const tx = await program.methods
+      .createProfile(name)
+      .accounts({
+        creator: provider.publicKey,
+        profile,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    console.log("Create profile success", tx);
+
+    editorCollaborator = collaboratorAddress(editor.publicKey);
+    viewerCollaborator = collaboratorAddress(viewer.publicKey);
+
+    for (const member of [editor, viewer]) {
+      const signature = await provider.connection.requestAirdrop(
+        member.publicKey,
+        anchor.web3.LAMPORTS_PER_SOL
+      );
+      await provider.connection.confirmTransaction(signature);
+    }
+  });
+
+  it("Invite collaborators successfully", async () => {
+    const tx = await program.methods
+      .inviteCollaborator(editor.publicKey, { editor: {} })
+      .accounts({
+        inviter: provider.publicKey,
+        inviterCollaborator: null,
+        profile,
+        collaborator: editorCollaborator,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    console.log("Your transaction signature", tx);
+
+    await program.methods
+      .inviteCollaborator(viewer.publicKey, { viewer: {} })
+      .accounts({
+        inviter: provider.publicKey,
+        inviterCollaborator: null,
+        profile,
+        collaborator: viewerCollaborator,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    const collaboratorAccount = await program.account.collaborator.fetch(
+      editorCollaborator
+    );
+    expect(collaboratorAccount.profile.toBase58()).to.equal(profile.toBase58());
+    expect(collaboratorAccount.member.toBase58()).to.equal(
+      editor.publicKey.toBase58()
+    );
+    expect(collaboratorAccount.role).to.deep.equal({ editor: {} });
+    expect(collaboratorAccount.invitedBy.toBase58()).to.equal(
+      provider.publicKey.toBase58()
+    );
+  });
+
+  it("Editor creates and toggles todo successfully", async () => {
+    const { index, todo } = await nextTodo();
+
+    const tx = await program.methods
+      .createTodo(content, null, { medium: {} }, [])
+      .accounts({
+        creator: editor.publicKey,
+        collaborator: editorCollaborator,
+        profile,
+        todo,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([editor])
+      .rpc();
+
+    console.log("Your transaction signature", tx);
+
+    await program.methods
+      .toggleTodo(new anchor.BN(index))
+      .accounts({
+        authority: editor.publicKey,
+        collaborator: editorCollaborator,
+        profile,
+        todo,
+      })
+      .signers([editor])
+      .rpc();
+
+    const todoAccount = await program.account.todo.fetch(todo);
+    expect(todoAccount.content).to.equal(content);
+    expect(todoAccount.completed).to.equal(true);
+  });
+
+  it("Create todo failed by viewer", async () => {
+    const { todo } = await nextTodo();
+
+    await withErrorTest(async () => {
+      try {
+        const tx = await program.methods
+          .createTodo(content, null, { medium: {} }, [])
+          .accounts({
+            creator: viewer.publicKey,
+            collaborator: viewerCollaborator,
+            profile,
+            todo,
+            systemProgram: anchor.web3.SystemProgram.programId,
+          })
+          .signers([viewer])
+          .rpc();
+
+        console.log("Your transaction signature", tx);
+
+        assert.ok(false);
+      } catch (_err) {
+        // console.log(_err);
+        assert.isTrue(_err instanceof anchor.AnchorError);
+        const err: anchor.AnchorError = _err;
+        assert.strictEqual(err.error.errorMessage, "Invalid authority");
+        assert.strictEqual(err.error.errorCode.code, "InvalidAuthority");
+        assert.strictEqual(
+          err.program.toString(),
+          program.programId.toString()
+        );
+      }
+    });
+  });
+
+  it("Revoke collaborator successfully", async () => {
+    const tx = await program.methods
+      .revokeCollaborator(editor.publicKey)
+      .accounts({
+        revoker: provider.publicKey,
+        revokerCollaborator: null,
+        profile,
+        invitedBy: provider.publicKey,
+        collaborator: editorCollaborator,
+      })
+      .rpc();
+
+    console.log("Your transaction signature", tx);
+
+    const collaboratorAccount = await program.account.collaborator.fetchNullable(
+      editorCollaborator
+    );
+    expect(collaboratorAccount).to.equal(null);
+
+    // the viewer leaves on its own
+    await program.methods
+      .revokeCollaborator(viewer.publicKey)
+      .accounts({
+        revoker: viewer.publicKey,
+        revokerCollaborator: viewerCollaborator,
+        profile,
+        invitedBy: provider.publicKey,
+        collaborator: viewerCollaborator,
+      })
+      .signers([viewer])
+      .rpc();
+  });
+});
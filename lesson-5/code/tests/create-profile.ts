@@ -35,7 +35,8 @@ describe("todo-app", () => {
     expect(profileAccount.authority.toBase58()).to.equal(
       provider.publicKey.toBase58()
     );
-    expect(profileAccount.todoCount).to.equal(0);
+    expect(profileAccount.todoCount.toNumber()).to.equal(0);
+    expect(profileAccount.nextTodoIndex.toNumber()).to.equal(0);
   });
 
   it("Create profile failed", async () => {
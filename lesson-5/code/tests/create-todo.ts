@@ -2,7 +2,7 @@ import * as anchor from "@coral-xyz/anchor";
 import { Program } from "@coral-xyz/anchor";
 import { TodoApp } from "../target/types/todo_app";
 import { assert, expect } from "chai";
-import { withErrorTest } from "./utils";
+import { todoIndexSeed, withErrorTest } from "./utils";
 
 describe("todo-app", () => {
   const provider = anchor.AnchorProvider.env();
@@ -35,17 +35,18 @@ describe("todo-app", () => {
 
   it("Create todo successfully", async () => {
     let profileAccount = await program.account.profile.fetch(profile);
-    const currentTodoCount = profileAccount.todoCount;
+    const currentTodoCount = profileAccount.nextTodoIndex.toNumber();
 
     const [todo] = anchor.web3.PublicKey.findProgramAddressSync(
-      [Buffer.from("todo"), profile.toBytes(), Buffer.from([currentTodoCount])],
+      [Buffer.from("todo"), profile.toBytes(), todoIndexSeed(currentTodoCount)],
       program.programId
     );
 
     const tx = await program.methods
-      .createTodo(content)
+      .createTodo(content, null, { medium: {} }, [])
       .accounts({
         creator: provider.publicKey,
+        collaborator: null,
         profile,
         todo,
         systemProgram: anchor.web3.SystemProgram.programId,
@@ -60,14 +61,17 @@ describe("todo-app", () => {
     expect(todoAccount.completed).to.equal(false);
 
     profileAccount = await program.account.profile.fetch(profile);
-    expect(profileAccount.todoCount).to.equal(currentTodoCount + 1);
+    expect(profileAccount.nextTodoIndex.toNumber()).to.equal(
+      currentTodoCount + 1
+    );
+    expect(profileAccount.todoCount.toNumber()).to.equal(1);
   });
 
   it("Create todo failed", async () => {
     withErrorTest(async () => {
       try {
         let profileAccount = await program.account.profile.fetch(profile);
-        const currentTodoCount = profileAccount.todoCount;
+        const currentTodoCount = profileAccount.nextTodoIndex.toNumber();
 
         const longContent = `
     Lorem Ipsum is simply dummy text of the printing and typesetting industry. Lorem Ipsum has been the industry's standard dummy text ever since the 1500s, when an unknown printer took a galley of type and scrambled it to make a type specimen book. It has survived not only five centuries, but also the leap into electronic typesetting, remaining essentially unchanged. It was popularised in the 1960s with the release of Letraset sheets containing Lorem Ipsum passages, and more recently with desktop publishing software like Aldus PageMaker including versions of Lorem Ipsum.
@@ -77,15 +81,16 @@ describe("todo-app", () => {
           [
             Buffer.from("todo"),
             profile.toBytes(),
-            Buffer.from([currentTodoCount]),
+            todoIndexSeed(currentTodoCount),
           ],
           program.programId
         );
 
         const tx = await program.methods
-          .createTodo(longContent)
+          .createTodo(longContent, null, { medium: {} }, [])
           .accounts({
             creator: provider.publicKey,
+            collaborator: null,
             profile,
             todo,
             systemProgram: anchor.web3.SystemProgram.programId,
@@ -126,7 +131,7 @@ describe("todo-app", () => {
     withErrorTest(async () => {
       try {
         let profileAccount = await program.account.profile.fetch(profile);
-        const currentTodoCount = profileAccount.todoCount;
+        const currentTodoCount = profileAccount.nextTodoIndex.toNumber();
 
         const content = `
     Lorem Ipsum is simply dummy text of the printing and typesetting industry..
@@ -136,15 +141,16 @@ describe("todo-app", () => {
           [
             Buffer.from("todo"),
             profile.toBytes(),
-            Buffer.from([currentTodoCount]),
+            todoIndexSeed(currentTodoCount),
           ],
           program.programId
         );
 
         const tx = await program.methods
-          .createTodo(content)
+          .createTodo(content, null, { medium: {} }, [])
           .accounts({
             creator: anotherPayer.publicKey,
+            collaborator: null,
             profile,
             todo,
             systemProgram: anchor.web3.SystemProgram.programId,
@@ -169,4 +175,84 @@ describe("todo-app", () => {
       }
     });
   });
+
+  it("Create todo with due date, priority and tags", async () => {
+    const profileAccount = await program.account.profile.fetch(profile);
+    const currentTodoCount = profileAccount.nextTodoIndex.toNumber();
+
+    const [todo] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("todo"), profile.toBytes(), todoIndexSeed(currentTodoCount)],
+      program.programId
+    );
+
+    const dueAt = Math.floor(Date.now() / 1000) + 24 * 60 * 60;
+
+    await program.methods
+      .createTodo(content, new anchor.BN(dueAt), { high: {} }, [
+        "bootcamp",
+        "solana",
+      ])
+      .accounts({
+        creator: provider.publicKey,
+        collaborator: null,
+        profile,
+        todo,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    const todoAccount = await program.account.todo.fetch(todo);
+
+    expect(todoAccount.dueAt.toNumber()).to.equal(dueAt);
+    expect(todoAccount.priority).to.deep.equal({ high: {} });
+    expect(todoAccount.tags).to.deep.equal(["bootcamp", "solana"]);
+    expect(todoAccount.createdAt.toNumber()).to.be.greaterThan(0);
+    expect(todoAccount.updatedAt.toNumber()).to.equal(
+      todoAccount.createdAt.toNumber()
+    );
+    expect(todoAccount.completedAt).to.equal(null);
+  });
+
+  it("Create todo failed by providing too many tags", async () => {
+    withErrorTest(async () => {
+      try {
+        const profileAccount = await program.account.profile.fetch(profile);
+        const currentTodoCount = profileAccount.nextTodoIndex.toNumber();
+
+        const [todo] = anchor.web3.PublicKey.findProgramAddressSync(
+          [
+            Buffer.from("todo"),
+            profile.toBytes(),
+            todoIndexSeed(currentTodoCount),
+          ],
+          program.programId
+        );
+
+        const tx = await program.methods
+          .createTodo(content, null, { low: {} }, ["a", "b", "c", "d", "e", "f"])
+          .accounts({
+            creator: provider.publicKey,
+            collaborator: null,
+            profile,
+            todo,
+            systemProgram: anchor.web3.SystemProgram.programId,
+          })
+          .rpc();
+
+        console.log("Your transaction signature", tx);
+
+        assert.ok(false);
+      } catch (_err) {
+        // console.log(_err);
+        assert.isTrue(_err instanceof anchor.AnchorError);
+        const err: anchor.AnchorError = _err;
+        assert.strictEqual(err.error.errorMessage, "Too many tags");
+        assert.strictEqual(err.error.errorCode.code, "TooManyTags");
+        assert.strictEqual(
+          err.program.toString(),
+          program.programId.toString()
+        );
+      }
+    });
+  });
 });
@@ -0,0 +1,110 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { TodoApp } from "../target/types/todo_app";
+import { assert, expect } from "chai";
+import { todoIndexSeed } from "./utils";
+
+describe("todo-app", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.TodoApp as Program<TodoApp>;
+  const name = "Khac Vy";
+
+  const content = "Do Solana bootcamp homework";
+
+  let profile: anchor.web3.PublicKey;
+
+  const findTodo = (index: number) =>
+    anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("todo"), profile.toBytes(), todoIndexSeed(index)],
+      program.programId
+    )[0];
+
+  const createTodo = async () => {
+    const profileAccount = await program.account.profile.fetch(profile);
+    const index = profileAccount.nextTodoIndex.toNumber();
+    const todo = findTodo(index);
+
+    await program.methods
+      .createTodo(content, null, { medium: {} }, [])
+      .accounts({
+        creator: provider.publicKey,
+        collaborator: null,
+        profile,
+        todo,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    return { index, todo };
+  };
+
+  before(async () => {
+    [profile] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("profile"), provider.publicKey.toBytes()],
+      program.programId
+    );
+
+    const tx = await program.methods
+      .createProfile(name)
+      .accounts({
+        creator: provider.publicKey,
+        profile,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    console.log("Create profile success", tx);
+  });
+
+  it("Delete todo successfully", async () => {
+    const { index, todo } = await createTodo();
+
+    const rent = await provider.connection.getBalance(todo);
+    const balanceBefore = await provider.connection.getBalance(
+      provider.publicKey
+    );
+
+    const tx = await program.methods
+      .deleteTodo(new anchor.BN(index))
+      .accounts({
+        authority: provider.publicKey,
+        collaborator: null,
+        profile,
+        todo,
+      })
+      .rpc();
+
+    console.log("Your transaction signature", tx);
+
+    const todoAccount = await program.account.todo.fetchNullable(todo);
+    expect(todoAccount).to.equal(null);
+
+    // the refund minus the transaction fee lands back on the authority
+    const balanceAfter = await provider.connection.getBalance(
+      provider.publicKey
+    );
+    assert.isAbove(balanceAfter, balanceBefore + rent - 10_000);
+  });
+
+  it("Create todo after delete does not reuse the deleted index", async () => {
+    const first = await createTodo();
+
+    await program.methods
+      .deleteTodo(new anchor.BN(first.index))
+      .accounts({
+        authority: provider.publicKey,
+        collaborator: null,
+        profile,
+        todo: first.todo,
+      })
+      .rpc();
+
+    const second = await createTodo();
+    expect(second.index).to.equal(first.index + 1);
+
+    const todoAccount = await program.account.todo.fetch(second.todo);
+    expect(todoAccount.content).to.equal(content);
+  });
+});
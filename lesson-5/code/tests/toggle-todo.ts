@@ -0,0 +1,125 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { TodoApp } from "../target/types/todo_app";
+import { assert, expect } from "chai";
+import { todoIndexSeed, withErrorTest } from "./utils";
+
+describe("todo-app", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.TodoApp as Program<TodoApp>;
+  const name = "Khac Vy";
+
+  const content = "Do Solana bootcamp homework";
+
+  let profile: anchor.web3.PublicKey;
+  let todo: anchor.web3.PublicKey;
+  let todoIndex: number;
+
+  before(async () => {
+    [profile] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("profile"), provider.publicKey.toBytes()],
+      program.programId
+    );
+
+    const tx = await program.methods
+      .createProfile(name)
+      .accounts({
+        creator: provider.publicKey,
+        profile,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    console.log("Create profile success", tx);
+
+    const profileAccount = await program.account.profile.fetch(profile);
+    todoIndex = profileAccount.nextTodoIndex.toNumber();
+
+    [todo] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("todo"), profile.toBytes(), todoIndexSeed(todoIndex)],
+      program.programId
+    );
+
+    const createTodoTx = await program.methods
+      .createTodo(content, null, { medium: {} }, [])
+      .accounts({
+        creator: provider.publicKey,
+        collaborator: null,
+        profile,
+        todo,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    console.log("Create todo success", createTodoTx);
+  });
+
+  it("Toggle todo successfully", async () => {
+    const tx = await program.methods
+      .toggleTodo(new anchor.BN(todoIndex))
+      .accounts({
+        authority: provider.publicKey,
+        collaborator: null,
+        profile,
+        todo,
+      })
+      .rpc();
+
+    console.log("Your transaction signature", tx);
+
+    let todoAccount = await program.account.todo.fetch(todo);
+    expect(todoAccount.completed).to.equal(true);
+
+    await program.methods
+      .toggleTodo(new anchor.BN(todoIndex))
+      .accounts({
+        authority: provider.publicKey,
+        collaborator: null,
+        profile,
+        todo,
+      })
+      .rpc();
+
+    todoAccount = await program.account.todo.fetch(todo);
+    expect(todoAccount.completed).to.equal(false);
+  });
+
+  it("Toggle todo failed by providing invalid authority", async () => {
+    const anotherPayer = anchor.web3.Keypair.generate();
+
+    await withErrorTest(async () => {
+      try {
+        const tx = await program.methods
+          .toggleTodo(new anchor.BN(todoIndex))
+          .accounts({
+            authority: anotherPayer.publicKey,
+            collaborator: null,
+            profile,
+            todo,
+          })
+          .signers([anotherPayer])
+          .rpc();
+
+        console.log("Your transaction signature", tx);
+
+        assert.ok(false);
+      } catch (_err) {
+        // console.log(_err);
+        assert.isTrue(_err instanceof anchor.AnchorError);
+        const err: anchor.AnchorError = _err;
+        assert.strictEqual(err.error.errorMessage, "Invalid authority");
+        assert.strictEqual(err.error.errorCode.number, 6002);
+        assert.strictEqual(err.error.errorCode.code, "InvalidAuthority");
+        assert.strictEqual(
+          err.program.toString(),
+          program.programId.toString()
+        );
+      }
+    });
+
+    const todoAccount = await program.account.todo.fetch(todo);
+    expect(todoAccount.completed).to.equal(false);
+  });
+});
@@ -0,0 +1,127 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { TodoApp } from "../target/types/todo_app";
+import { assert, expect } from "chai";
+import { withErrorTest } from "./utils";
+
+describe("todo-app", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.TodoApp as Program<TodoApp>;
+  const name = "Khac Vy";
+
+  const newAuthority = anchor.web3.Keypair.generate();
+
+  let profile: anchor.web3.PublicKey;
+  let pendingAuthority: anchor.web3.PublicKey;
+
+  before(async () => {
+    [profile] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("profile"), provider.publicKey.toBytes()],
+      program.programId
+    );
+
+    [pendingAuthority] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("pending_authority"), profile.toBytes()],
+      program.programId
+    );
+
+    const tx = await program.methods
+      .createProfile(name)
+      .accounts({
+        creator: provider.publicKey,
+        profile,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    console.log("Create profile success", tx);
+  });
+
+  it("Propose authority successfully", async () => {
+    const tx = await program.methods
+      .proposeAuthority(newAuthority.publicKey)
+      .accounts({
+        authority: provider.publicKey,
+        profile,
+        pendingAuthority,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    console.log("Your transaction signature", tx);
+
+    const pendingAccount = await program.account.pendingAuthority.fetch(
+      pendingAuthority
+    );
+    expect(pendingAccount.profile.toBase58()).to.equal(profile.toBase58());
+    expect(pendingAccount.newAuthority.toBase58()).to.equal(
+      newAuthority.publicKey.toBase58()
+    );
+
+    // the profile does not move until the new authority accepts
+    const profileAccount = await program.account.profile.fetch(profile);
+    expect(profileAccount.authority.toBase58()).to.equal(
+      provider.publicKey.toBase58()
+    );
+  });
+
+  it("Accept authority failed by providing invalid signer", async () => {
+    const anotherSigner = anchor.web3.Keypair.generate();
+
+    await withErrorTest(async () => {
+      try {
+        const tx = await program.methods
+          .acceptAuthority()
+          .accounts({
+            newAuthority: anotherSigner.publicKey,
+            proposedBy: provider.publicKey,
+            profile,
+            pendingAuthority,
+          })
+          .signers([anotherSigner])
+          .rpc();
+
+        console.log("Your transaction signature", tx);
+
+        assert.ok(false);
+      } catch (_err) {
+        // console.log(_err);
+        assert.isTrue(_err instanceof anchor.AnchorError);
+        const err: anchor.AnchorError = _err;
+        assert.strictEqual(err.error.errorMessage, "Invalid authority");
+        assert.strictEqual(err.error.errorCode.code, "InvalidAuthority");
+        assert.strictEqual(
+          err.program.toString(),
+          program.programId.toString()
+        );
+      }
+    });
+  });
+
+  it("Accept authority successfully", async () => {
+    const tx = await program.methods
+      .acceptAuthority()
+      .accounts({
+        newAuthority: newAuthority.publicKey,
+        proposedBy: provider.publicKey,
+        profile,
+        pendingAuthority,
+      })
+      .signers([newAuthority])
+      .rpc();
+
+    console.log("Your transaction signature", tx);
+
+    const profileAccount = await program.account.profile.fetch(profile);
+    expect(profileAccount.authority.toBase58()).to.equal(
+      newAuthority.publicKey.toBase58()
+    );
+
+    const pendingAccount = await program.account.pendingAuthority.fetchNullable(
+      pendingAuthority
+    );
+    expect(pendingAccount).to.equal(null);
+  });
+});
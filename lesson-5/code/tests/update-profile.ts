@@ -0,0 +1,79 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { TodoApp } from "../target/types/todo_app";
+import { assert, expect } from "chai";
+import { withErrorTest } from "./utils";
+
+describe("todo-app", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.TodoApp as Program<TodoApp>;
+  const name = "Khac Vy";
+
+  let profile: anchor.web3.PublicKey;
+
+  before(async () => {
+    [profile] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("profile"), provider.publicKey.toBytes()],
+      program.programId
+    );
+
+    const tx = await program.methods
+      .createProfile(name)
+      .accounts({
+        creator: provider.publicKey,
+        profile,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    console.log("Create profile success", tx);
+  });
+
+  it("Update profile successfully", async () => {
+    const newName = "Tran Khac Vy";
+
+    const tx = await program.methods
+      .updateProfile(newName)
+      .accounts({
+        authority: provider.publicKey,
+        profile,
+      })
+      .rpc();
+
+    console.log("Your transaction signature", tx);
+
+    const profileAccount = await program.account.profile.fetch(profile);
+    expect(profileAccount.name).to.equal(newName);
+  });
+
+  it("Update profile failed", async () => {
+    await withErrorTest(async () => {
+      try {
+        const tx = await program.methods
+          .updateProfile("a".repeat(101))
+          .accounts({
+            authority: provider.publicKey,
+            profile,
+          })
+          .rpc();
+
+        console.log("Your transaction signature", tx);
+
+        assert.ok(false);
+      } catch (_err) {
+        // console.log(_err);
+        assert.isTrue(_err instanceof anchor.AnchorError);
+        const err: anchor.AnchorError = _err;
+        assert.strictEqual(err.error.errorMessage, "Name is too long");
+        assert.strictEqual(err.error.errorCode.number, 6000);
+        assert.strictEqual(err.error.errorCode.code, "NameTooLong");
+        assert.strictEqual(
+          err.program.toString(),
+          program.programId.toString()
+        );
+      }
+    });
+  });
+});
@@ -0,0 +1,114 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { TodoApp } from "../target/types/todo_app";
+import { assert, expect } from "chai";
+import { todoIndexSeed, withErrorTest } from "./utils";
+
+describe("todo-app", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.TodoApp as Program<TodoApp>;
+  const name = "Khac Vy";
+
+  const content = "Do Solana bootcamp homwork";
+
+  let profile: anchor.web3.PublicKey;
+  let todo: anchor.web3.PublicKey;
+  let todoIndex: number;
+
+  before(async () => {
+    [profile] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("profile"), provider.publicKey.toBytes()],
+      program.programId
+    );
+
+    const tx = await program.methods
+      .createProfile(name)
+      .accounts({
+        creator: provider.publicKey,
+        profile,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    console.log("Create profile success", tx);
+
+    const profileAccount = await program.account.profile.fetch(profile);
+    todoIndex = profileAccount.nextTodoIndex.toNumber();
+
+    [todo] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("todo"), profile.toBytes(), todoIndexSeed(todoIndex)],
+      program.programId
+    );
+
+    const createTodoTx = await program.methods
+      .createTodo(content, null, { medium: {} }, [])
+      .accounts({
+        creator: provider.publicKey,
+        collaborator: null,
+        profile,
+        todo,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    console.log("Create todo success", createTodoTx);
+  });
+
+  it("Update todo successfully", async () => {
+    const newContent = "Do Solana bootcamp homework";
+
+    const tx = await program.methods
+      .updateTodo(new anchor.BN(todoIndex), newContent, null, null)
+      .accounts({
+        authority: provider.publicKey,
+        collaborator: null,
+        profile,
+        todo,
+      })
+      .rpc();
+
+    console.log("Your transaction signature", tx);
+
+    const todoAccount = await program.account.todo.fetch(todo);
+    expect(todoAccount.content).to.equal(newContent);
+    expect(todoAccount.completed).to.equal(false);
+  });
+
+  it("Update todo failed", async () => {
+    await withErrorTest(async () => {
+      try {
+        const tx = await program.methods
+          .updateTodo(
+            new anchor.BN(todoIndex),
+            "a".repeat(201),
+            null,
+            null
+          )
+          .accounts({
+            authority: provider.publicKey,
+            collaborator: null,
+            profile,
+            todo,
+          })
+          .rpc();
+
+        console.log("Your transaction signature", tx);
+
+        assert.ok(false);
+      } catch (_err) {
+        // console.log(_err);
+        assert.isTrue(_err instanceof anchor.AnchorError);
+        const err: anchor.AnchorError = _err;
+        assert.strictEqual(err.error.errorMessage, "Content is too long");
+        assert.strictEqual(err.error.errorCode.number, 6001);
+        assert.strictEqual(err.error.errorCode.code, "ContentTooLong");
+        assert.strictEqual(
+          err.program.toString(),
+          program.programId.toString()
+        );
+      }
+    });
+  });
+});
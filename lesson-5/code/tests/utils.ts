@@ -1,3 +1,5 @@
+import * as anchor from "@coral-xyz/anchor";
+
 export const withErrorTest = async (callback) => {
   try {
     await callback();
@@ -5,3 +7,15 @@ export const withErrorTest = async (callback) => {
     throw err;
   }
 };
+
+// Minimal little-endian encoding of a todo index, matching `Todo::index_seed`.
+export const todoIndexSeed = (index: number) => {
+  const bytes = new anchor.BN(index).toArrayLike(Buffer, "le", 8);
+
+  let len = bytes.length;
+  while (len > 1 && bytes[len - 1] === 0) {
+    len--;
+  }
+
+  return bytes.subarray(0, len);
+};